@@ -0,0 +1,71 @@
+//! Generates the `__if_input!` macro, which tells the `solution!` macro which days have an input that can be read.
+//! Inputs are not part of the repository, so this is decided when the crate is built.
+
+use std::{env, fs, path::Path};
+
+const INPUTS_DIR: &str = "data/inputs";
+const KEY_ENV: &str = "AOC_INPUT_KEY";
+const KEY_FILE: &str = ".aoc-input-key";
+
+fn main() {
+    // a directory is watched recursively, this includes inputs that are downloaded into an empty file.
+    // every change reruns this script, which rebuilds the library and all solutions, e.g. after `cargo download`.
+    println!("cargo:rerun-if-changed={INPUTS_DIR}");
+    println!("cargo:rerun-if-env-changed={KEY_ENV}");
+    // cargo reruns the script on every build while a watched file is missing, so the key file is only
+    // watched once it exists. A key file that is added later is picked up with the next change to the inputs.
+    if Path::new(KEY_FILE).exists() {
+        println!("cargo:rerun-if-changed={KEY_FILE}");
+    }
+
+    // encrypted inputs count as well if there is a key, they are decrypted when the input is read.
+    let has_key = env::var(KEY_ENV).is_ok_and(|key| !key.trim().is_empty())
+        || fs::metadata(KEY_FILE).is_ok_and(|m| m.len() > 0);
+
+    let mut days: Vec<u8> = fs::read_dir(INPUTS_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|entry| entry.metadata().is_ok_and(|m| m.len() > 0))
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    let name = match name.strip_suffix(".enc") {
+                        Some(name) if has_key => name,
+                        Some(_) => return None,
+                        None => &name,
+                    };
+                    name.strip_suffix(".txt")?.parse().ok()
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    days.sort_unstable();
+    days.dedup();
+
+    let arms: String = days
+        .iter()
+        // days can be written with or without padding, e.g. `solution!(1)` or `solution!(01)`.
+        .flat_map(|day| {
+            if *day < 10 {
+                vec![day.to_string(), format!("{day:02}")]
+            } else {
+                vec![day.to_string()]
+            }
+        })
+        .map(|day| {
+            format!("    ({day}, {{ $($then:tt)* }} else {{ $($else:tt)* }}) => {{ $($then)* }};\n")
+        })
+        .collect();
+
+    let source = format!(
+        "#[doc(hidden)]
+#[macro_export]
+macro_rules! __if_input {{
+{arms}    ($day:tt, {{ $($then:tt)* }} else {{ $($else:tt)* }}) => {{ $($else)* }};
+}}
+"
+    );
+
+    let path = Path::new(&env::var("OUT_DIR").unwrap()).join("inputs.rs");
+    fs::write(path, source).unwrap();
+}
@@ -1,4 +1,10 @@
-advent_of_code::solution!(2);
+use advent_of_code::template::fuzz::Rng;
+
+advent_of_code::solution!(2;
+    fuzz(generate_reports, 2 => part_two_naive),
+    alternatives(2 => [part_two_naive]),
+    input_tests(),
+);
 
 trait IsSafe {
     fn is_safe(&self, damped: bool) -> bool;
@@ -20,7 +26,7 @@ impl IsSafe for Vec<u32> {
         {
             true
         } else if !damped {
-            return false;
+            false
         } else {
             (0..self.len())
                 .map(|i| self.iter().take(i).chain(self.iter().skip(i + 1)))
@@ -29,6 +35,59 @@ impl IsSafe for Vec<u32> {
     }
 }
 
+/// Index of the first step that is not in `1..=3` in the given direction, if any.
+fn first_bad_step(report: &[u32], direction: i32) -> Option<usize> {
+    report
+        .windows(2)
+        .position(|w| !(1..=3).contains(&((w[1] as i32 - w[0] as i32) * direction)))
+}
+
+/// Checks a report with the problem dampener without trying every removal:
+/// only the two levels of the first bad step can make the report safe when removed.
+fn is_safe_dampened(report: &[u32]) -> bool {
+    [1, -1].into_iter().any(|direction| {
+        let Some(i) = first_bad_step(report, direction) else {
+            return true;
+        };
+        [i, i + 1].into_iter().any(|skip| {
+            let damped: Vec<u32> = report
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != skip)
+                .map(|(_, n)| *n)
+                .collect();
+            first_bad_step(&damped, direction).is_none()
+        })
+    })
+}
+
+/// Generates mostly monotonic reports with the occasional outlier.
+fn generate_reports(rng: &mut Rng) -> String {
+    (0..rng.range(1..=6))
+        .map(|_| {
+            let increasing = rng.one_in(2);
+            let mut level = rng.range(70..=90);
+            (0..rng.range(1..=8))
+                .map(|_| {
+                    let step = if rng.one_in(5) {
+                        rng.range(0..=8)
+                    } else {
+                        rng.range(1..=3)
+                    };
+                    level = if increasing ^ rng.one_in(8) {
+                        level + step
+                    } else {
+                        level - step
+                    };
+                    level.to_string()
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
     input.lines().try_fold(0, |acc, line| {
         let nums: Vec<u32> = line
@@ -44,6 +103,21 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
+    input.lines().try_fold(0, |acc, line| {
+        let nums: Vec<u32> = line
+            .split(' ')
+            .map(|n| n.parse::<u32>().unwrap())
+            .collect::<Vec<u32>>();
+        if is_safe_dampened(&nums) {
+            Some(acc + 1)
+        } else {
+            Some(acc)
+        }
+    })
+}
+
+/// Reference implementation of part two that tries every possible removal.
+pub fn part_two_naive(input: &str) -> Option<u32> {
     input.lines().try_fold(0, |acc, line| {
         let nums: Vec<u32> = line
             .split(' ')
@@ -72,4 +146,10 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(4));
     }
+
+    #[test]
+    fn test_part_two_naive() {
+        let result = part_two_naive(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(4));
+    }
 }
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(3; input_tests());
 
 #[derive(Debug, Clone)]
 struct Operation {
@@ -22,6 +22,7 @@ trait Parser<'a, Output> {
     {
         BoxedParser::new(map(self, map_fn))
     }
+    #[allow(dead_code)]
     fn pred<F>(self, pred_fn: F) -> BoxedParser<'a, Output>
     where
         Self: Sized + 'a,
@@ -30,6 +31,7 @@ trait Parser<'a, Output> {
     {
         BoxedParser::new(pred(self, pred_fn))
     }
+    #[allow(dead_code)]
     fn and_then<F, NextParser, NewOutput>(self, f: F) -> BoxedParser<'a, NewOutput>
     where
         Self: Sized + 'a,
@@ -57,6 +59,7 @@ where
     }
 }
 
+#[allow(dead_code)]
 fn and_then<'a, P, F, A, B, NextP>(parser: P, f: F) -> impl Parser<'a, B>
 where
     P: Parser<'a, A>,
@@ -188,7 +191,7 @@ where
     }
 }
 
-fn any_char(input: &str) -> ParseResult<char> {
+fn any_char(input: &str) -> ParseResult<'_, char> {
     match input.chars().next() {
         Some(next) => Ok((&input[next.len_utf8()..], next)),
         _ => Err(input),
@@ -326,7 +329,9 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", DAY, 2,
+        ));
+        assert_eq!(result, Some(48));
     }
 }
@@ -1,8 +1,6 @@
-use std::collections::HashSet;
-
 use itertools::Itertools;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(4; alternatives(1 => [part_one_scan]), input_tests());
 
 struct WordSearch {
     grid: Vec<Vec<u8>>,
@@ -133,10 +131,47 @@ pub fn part_one(input: &str) -> Option<u32> {
     )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+/// Alternative to `part_one` that walks the eight directions from every `X` in place,
+/// without collecting the matched positions.
+pub fn part_one_scan(input: &str) -> Option<u32> {
     let puzzle = WordSearch::new(input)?;
+    let height = puzzle.grid.len() as i32;
+    let width = puzzle.line_len as i32;
     let mut count = 0;
-    let mut expected: HashSet<&usize> = HashSet::new();
+
+    for v in 0..height {
+        for h in 0..width {
+            if puzzle.grid[v as usize][h as usize] != b'X' {
+                continue;
+            }
+            for (dh, dv) in [
+                (1, 0),
+                (-1, 0),
+                (0, 1),
+                (0, -1),
+                (1, 1),
+                (-1, -1),
+                (1, -1),
+                (-1, 1),
+            ] {
+                let found = b"MAS".iter().enumerate().all(|(i, c)| {
+                    let (h, v) = (h + dh * (i as i32 + 1), v + dv * (i as i32 + 1));
+                    (0..width).contains(&h)
+                        && (0..height).contains(&v)
+                        && puzzle.grid[v as usize][h as usize] == *c
+                });
+                if found {
+                    count += 1;
+                }
+            }
+        }
+    }
+
+    Some(count)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let puzzle = WordSearch::new(input)?;
     Some(
         [
             SomeHelpers::lk_search,
@@ -165,6 +200,12 @@ mod tests {
         assert_eq!(result, Some(18));
     }
 
+    #[test]
+    fn test_part_one_scan() {
+        let result = part_one_scan(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(result, Some(18));
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
@@ -1,12 +1,10 @@
-advent_of_code::solution!(5);
+advent_of_code::solution!(5; input_tests());
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::ops::Deref;
 use std::ops::DerefMut;
 
-use itertools::WhileSome;
-
 #[derive(Clone, Debug)]
 struct OrderingRules {
     rules: HashMap<u32, HashSet<u32>>,
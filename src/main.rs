@@ -1,144 +1,326 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
-use args::{parse, AppArguments};
+use std::{io, process};
+
+use advent_of_code::template::allocations::AllocTracking;
+use advent_of_code::template::commands::{
+    all, audit_data, bench_compare, check_examples, download, extract_examples, fuzz_check, hooks,
+    inputs, leaderboard, read, report, scaffold, solve, status, test, time,
+};
+use advent_of_code::template::error::Error;
+use advent_of_code::template::DaySet;
+use args::{AppArguments, Cli, HooksAction, InputsAction};
+use clap::{CommandFactory, Parser};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{report::Format, Day, DaySet};
+    use clap::{Parser, Subcommand};
+    use clap_complete::Shell;
+
+    /// Scaffold, run and benchmark Advent of Code solutions.
+    #[derive(Parser)]
+    #[command(name = "advent_of_code")]
+    pub struct Cli {
+        #[command(subcommand)]
+        pub command: AppArguments,
+    }
 
+    #[derive(Subcommand)]
     pub enum AppArguments {
-        Download {
+        /// Download the input and puzzle description of a set of days.
+        Download { days: DaySet },
+        /// Render the puzzle description of a day, downloading it if it is missing.
+        Read {
             day: Day,
+            /// Only show one part of the puzzle.
+            #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+            part: Option<u8>,
         },
-        Read {
+        /// Write the examples of a downloaded puzzle to the example files and tests of a day.
+        ExtractExamples {
             day: Day,
+            /// Overwrite existing example files without asking.
+            #[arg(long)]
+            yes: bool,
         },
+        /// Create the solution, input and example files of a set of days.
         Scaffold {
-            day: Day,
+            days: DaySet,
+            /// Download the input and puzzle before scaffolding.
+            #[arg(long)]
             download: bool,
+            /// Replace an existing solution file.
+            #[arg(long)]
             overwrite: bool,
+            /// Name of the solution template, see the `templates/` directory.
+            #[arg(long)]
+            template: Option<String>,
         },
+        /// Run the solutions of a set of days.
         Solve {
-            day: Day,
+            days: DaySet,
+            /// Build with optimizations.
+            #[arg(long)]
             release: bool,
+            /// Profile heap allocations with DHAT, implies an optimized build.
+            #[arg(long, conflicts_with = "release")]
             dhat: bool,
+            /// Submit the answer of a part with aoc-cli.
+            #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
             submit: Option<u8>,
         },
+        /// Run the solutions of all days, or of a set of days.
         All {
+            days: Option<DaySet>,
+            /// Build with optimizations.
+            #[arg(long)]
             release: bool,
         },
+        /// Benchmark the solutions of all days that were not benchmarked yet, or of a set of days.
         Time {
+            days: Option<DaySet>,
+            /// Benchmark all days, including the ones with stored benchmarks.
+            #[arg(long, conflicts_with = "days")]
             all: bool,
-            day: Option<Day>,
+            /// Store the benchmarks and update the readme.
+            #[arg(long)]
             store: bool,
+            /// Profile heap allocations with DHAT.
+            #[arg(long, conflicts_with = "alloc")]
+            dhat: bool,
+            /// Count allocations while benchmarking.
+            #[arg(long)]
+            alloc: bool,
+            /// Show the stored benchmark history instead of benchmarking.
+            #[arg(long, conflicts_with_all = ["all", "store", "dhat", "alloc"])]
+            history: bool,
+        },
+        /// Bench the alternative implementations of a day against its main implementation.
+        BenchCompare { day: Day },
+        /// Show a private leaderboard.
+        Leaderboard {
+            /// Id of the leaderboard, defaults to `AOC_LEADERBOARD_ID`.
+            id: Option<String>,
+            /// Show the times of a day.
+            #[arg(long)]
+            day: Option<Day>,
+            /// Only use the cached leaderboard.
+            #[arg(long)]
+            offline: bool,
+        },
+        /// Show the progress of every day.
+        Status {
+            /// Print the progress as JSON.
+            #[arg(long)]
+            json: bool,
         },
+        /// Render the stored benchmarks as markdown, CSV or HTML.
+        Report {
+            /// Format of the report, defaults to the extension of the output path.
+            #[arg(long)]
+            format: Option<Format>,
+            /// Path of the report, the report is printed if not set.
+            output: Option<String>,
+        },
+        /// Check the solution of a day against its reference implementations with generated inputs.
+        FuzzCheck {
+            day: Day,
+            /// Number of generated inputs.
+            #[arg(long)]
+            iterations: Option<u64>,
+            /// Seed of the input generator, to reproduce a run.
+            #[arg(long)]
+            seed: Option<u64>,
+        },
+        /// Scaffold, download and read the puzzle of today.
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            /// Wait for the next puzzle to unlock first.
+            #[arg(long)]
+            wait: bool,
+        },
+        /// Run the tests of the solutions of all days, or of a set of days.
+        Test { days: Option<DaySet> },
+        /// Run the example tests of all days, or of a set of days, and show which parts pass.
+        CheckExamples {
+            days: Option<DaySet>,
+            /// Print the results as JSON.
+            #[arg(long)]
+            json: bool,
+        },
+        /// Encrypt or decrypt the inputs, so they can be committed to a private repository.
+        Inputs {
+            #[command(subcommand)]
+            action: InputsAction,
+        },
+        /// Check the staged files for puzzle inputs and descriptions, which must not be published.
+        AuditData {
+            /// Check every tracked file instead of the staged files.
+            #[arg(long)]
+            all: bool,
+        },
+        /// Manage the git pre-commit hook that runs `audit-data`.
+        Hooks {
+            #[command(subcommand)]
+            action: HooksAction,
+        },
+        /// Print a shell completion script.
+        Completions { shell: Shell },
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
-
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
+    #[derive(Subcommand)]
+    pub enum InputsAction {
+        /// Write an encrypted copy of the inputs of all days, or of a set of days.
+        Encrypt { days: Option<DaySet> },
+        /// Write the inputs of all days, or of a set of days, from their encrypted copy.
+        Decrypt {
+            days: Option<DaySet>,
+            /// Replace inputs that differ from their encrypted copy.
+            #[arg(long)]
+            force: bool,
+        },
+        /// Create a new key in `.aoc-input-key`.
+        Keygen,
+    }
 
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
-                }
-            }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
-        };
+    #[derive(Subcommand)]
+    pub enum HooksAction {
+        /// Install a pre-commit hook that refuses commits with puzzle inputs or descriptions.
+        Install,
+        /// Remove the pre-commit hook.
+        Uninstall,
+    }
+}
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
+fn main() {
+    // invalid arguments exit with the status of `Error::Usage`, help is printed with status 0.
+    let cli = Cli::parse();
 
-        Ok(app_args)
+    if let Err(e) = run(cli.command) {
+        eprintln!("Error: {e}");
+        process::exit(e.exit_code());
     }
 }
 
-fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+fn run(args: AppArguments) -> Result<(), Error> {
+    match args {
+        AppArguments::All { days, release } => {
+            all::handle(&days.unwrap_or_else(DaySet::all), release)
+        }
+        AppArguments::Time { days, history, .. } if history => {
+            time::handle_history(days.as_ref());
+            Ok(())
+        }
+        AppArguments::Time {
+            days,
+            all,
+            store,
+            dhat,
+            alloc,
+            ..
+        } => {
+            let alloc_tracking = match (dhat, alloc) {
+                (true, _) => AllocTracking::Dhat,
+                (false, true) => AllocTracking::Counting,
+                (false, false) => AllocTracking::Off,
+            };
+            time::handle(days.as_ref(), all, store, alloc_tracking)
+        }
+        AppArguments::Download { days } => days.iter().try_for_each(download::handle),
+        AppArguments::Read { day, part } => read::handle(day, part),
+        AppArguments::ExtractExamples { day, yes } => extract_examples::handle(day, yes),
+        AppArguments::Scaffold {
+            days,
+            download,
+            overwrite,
+            template,
+        } => days.iter().try_for_each(|day| {
+            // download first, so the template can use the title of the puzzle.
+            if download {
+                download::handle(day)?;
+            }
+            scaffold::handle(day, overwrite, template.as_deref())
+        }),
+        AppArguments::Solve {
+            days,
+            release,
+            dhat,
+            submit,
+        } => solve::handle(&days, release, dhat, submit),
+        AppArguments::BenchCompare { day } => bench_compare::handle(day),
+        AppArguments::Leaderboard { id, day, offline } => leaderboard::handle(id, day, offline),
+        AppArguments::Report { format, output } => report::handle(format, output),
+        AppArguments::Status { json } => status::handle(json),
+        AppArguments::FuzzCheck {
+            day,
+            iterations,
+            seed,
+        } => fuzz_check::handle(day, iterations, seed),
+        #[cfg(feature = "today")]
+        AppArguments::Today { wait } => today::handle(wait),
+        AppArguments::Test { days } => test::handle(&days.unwrap_or_else(DaySet::all)),
+        AppArguments::CheckExamples { days, json } => {
+            check_examples::handle(&days.unwrap_or_else(DaySet::all), json)
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+        AppArguments::Inputs { action } => match action {
+            InputsAction::Encrypt { days } => {
+                inputs::handle_encrypt(&days.unwrap_or_else(DaySet::all))
             }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+            InputsAction::Decrypt { days, force } => {
+                inputs::handle_decrypt(&days.unwrap_or_else(DaySet::all), force)
             }
+            InputsAction::Keygen => inputs::handle_keygen(),
+        },
+        AppArguments::AuditData { all } => audit_data::handle(all),
+        AppArguments::Hooks { action } => match action {
+            HooksAction::Install => hooks::handle_install(),
+            HooksAction::Uninstall => hooks::handle_uninstall(),
         },
-    };
+        AppArguments::Completions { shell } => {
+            clap_complete::generate(
+                shell,
+                &mut Cli::command(),
+                "advent_of_code",
+                &mut io::stdout(),
+            );
+            Ok(())
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::Cli;
+    use clap::{error::ErrorKind, CommandFactory, Parser};
+
+    #[test]
+    fn cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn rejects_invalid_flag_combinations() {
+        let parse = |args: &[&str]| Cli::try_parse_from(args).err().map(|e| e.kind());
+
+        assert_eq!(parse(&["aoc", "solve", "1", "--release"]), None);
+        assert_eq!(
+            parse(&["aoc", "solve", "1", "--dhat", "--release"]),
+            Some(ErrorKind::ArgumentConflict)
+        );
+        assert_eq!(
+            parse(&["aoc", "time", "--history", "--store"]),
+            Some(ErrorKind::ArgumentConflict)
+        );
+        assert_eq!(
+            parse(&["aoc", "solve", "1", "--submit", "3"]),
+            Some(ErrorKind::ValueValidation)
+        );
+        assert_eq!(
+            parse(&["aoc", "sovle", "1"]),
+            Some(ErrorKind::InvalidSubcommand)
+        );
+    }
 }
@@ -0,0 +1,294 @@
+//! Heap allocation statistics of a single solution part.
+//! Stats are printed by the solution binaries next to the part's timing and parsed back by `run_multi`.
+
+use std::{collections::HashMap, fmt::Display};
+use tinyjson::JsonValue;
+
+#[cfg(feature = "alloc-stats")]
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+#[cfg(all(feature = "dhat-heap", feature = "alloc-stats"))]
+compile_error!("the `dhat-heap` and `alloc-stats` features both install a global allocator and can not be combined.");
+
+#[cfg(feature = "dhat-heap")]
+use crate::template::Day;
+
+/// Allocation statistics of the first (non-benched) run of a part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations.
+    pub blocks: u64,
+    /// Number of allocated bytes in total.
+    pub bytes: u64,
+    /// Maximum number of bytes alive at once.
+    pub peak_bytes: u64,
+}
+
+/// How allocations of a solution run are tracked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AllocTracking {
+    Off,
+    /// Profile with DHAT (`dhat-heap` feature, `dhat` profile).
+    Dhat,
+    /// Count allocations with [`CountingAlloc`] (`alloc-stats` feature, `release` profile).
+    Counting,
+}
+
+impl AllocStats {
+    /// Parses the stats from a line of solution output, e.g. `Part 1: 42 (1.2ms) [3 allocs, 276 B total, 232 B peak]`.
+    pub fn from_output_line(line: &str) -> Option<Self> {
+        let start = line.rfind('[')?;
+        let stats = line[start + 1..].trim_end().strip_suffix(']')?;
+        let mut values = stats.split(", ");
+
+        let blocks = values.next()?.strip_suffix(" allocs")?.parse().ok()?;
+        let bytes = values.next()?.strip_suffix(" B total")?.parse().ok()?;
+        let peak_bytes = values.next()?.strip_suffix(" B peak")?.parse().ok()?;
+
+        Some(Self {
+            blocks,
+            bytes,
+            peak_bytes,
+        })
+    }
+
+    /// Human readable summary, e.g. `3 · 276 B`.
+    pub fn summary(&self) -> String {
+        format!("{} · {}", self.blocks, format_bytes(self.bytes))
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{} allocs, {} B total, {} B peak]",
+            self.blocks, self.bytes, self.peak_bytes
+        )
+    }
+}
+
+/// Formats a byte count with binary units, e.g. `1.2 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+
+    for next_unit in &UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next_unit;
+    }
+
+    format!("{value:.1} {unit}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Path of the DHAT profile written for a part, e.g. `data/dhat/01-1.json`.
+#[cfg(feature = "dhat-heap")]
+pub fn dhat_file_path(day: Day, part: u8) -> String {
+    format!("data/dhat/{day}-{part}.json")
+}
+
+/// Starts a DHAT heap profiler that writes its report to [`dhat_file_path`].
+#[cfg(feature = "dhat-heap")]
+pub fn start_dhat_profiler(day: Day, part: u8) -> dhat::Profiler {
+    let _ = std::fs::create_dir_all("data/dhat");
+    dhat::Profiler::builder()
+        .file_name(dhat_file_path(day, part))
+        .build()
+}
+
+/// A global allocator that forwards to the system allocator and counts allocations.
+/// Installed by `solution!` when the `alloc-stats` feature is enabled.
+#[cfg(feature = "alloc-stats")]
+pub struct CountingAlloc;
+
+#[cfg(feature = "alloc-stats")]
+static BLOCKS: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "alloc-stats")]
+static BYTES: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "alloc-stats")]
+static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "alloc-stats")]
+static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+#[cfg(feature = "alloc-stats")]
+static BASELINE_BYTES: AtomicU64 = AtomicU64::new(0);
+
+#[cfg(feature = "alloc-stats")]
+impl CountingAlloc {
+    fn record_alloc(size: usize) {
+        BLOCKS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        let live = LIVE_BYTES.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+        PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        LIVE_BYTES.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record_dealloc(layout.size());
+    }
+
+    // a reallocation is counted as a new block, same as DHAT does.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::record_dealloc(layout.size());
+            Self::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Resets the allocation counters before running a part.
+/// Memory that is alive at this point does not count towards the peak.
+pub fn reset_counters() {
+    #[cfg(feature = "alloc-stats")]
+    {
+        let live = LIVE_BYTES.load(Ordering::Relaxed);
+        BLOCKS.store(0, Ordering::Relaxed);
+        BYTES.store(0, Ordering::Relaxed);
+        PEAK_BYTES.store(live, Ordering::Relaxed);
+        BASELINE_BYTES.store(live, Ordering::Relaxed);
+    }
+}
+
+/// Reads the statistics of the currently running heap profiler or allocation counter, if any.
+pub fn current_stats() -> Option<AllocStats> {
+    #[cfg(feature = "dhat-heap")]
+    {
+        let stats = dhat::HeapStats::get();
+        Some(AllocStats {
+            blocks: stats.total_blocks,
+            bytes: stats.total_bytes,
+            peak_bytes: stats.max_bytes as u64,
+        })
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    {
+        Some(AllocStats {
+            blocks: BLOCKS.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+            peak_bytes: PEAK_BYTES.load(Ordering::Relaxed) - BASELINE_BYTES.load(Ordering::Relaxed),
+        })
+    }
+
+    #[cfg(not(any(feature = "dhat-heap", feature = "alloc-stats")))]
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&AllocStats> for JsonValue {
+    fn from(value: &AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert("blocks".into(), JsonValue::Number(value.blocks as f64));
+            map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+            map.insert(
+                "peak_bytes".into(),
+                JsonValue::Number(value.peak_bytes as f64),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected allocation stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!(
+                    "Expected allocation stats to have numeric `{key}`."
+                ))
+        };
+
+        Ok(AllocStats {
+            blocks: number("blocks")?,
+            bytes: number("bytes")?,
+            peak_bytes: number("peak_bytes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, AllocStats};
+
+    #[test]
+    fn round_trips_output_lines() {
+        let stats = AllocStats {
+            blocks: 3,
+            bytes: 276,
+            peak_bytes: 232,
+        };
+        let line = format!("Part 1: [1, 2] (1.2ms @ 10 samples) {stats}");
+        assert_eq!(AllocStats::from_output_line(&line), Some(stats));
+    }
+
+    #[test]
+    fn ignores_lines_without_stats() {
+        assert_eq!(
+            AllocStats::from_output_line("Part 1: 42 (1.2ms @ 10 samples)"),
+            None
+        );
+        assert_eq!(AllocStats::from_output_line("Part 1: [1, 2] (1.2ms)"), None);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(276), "276 B");
+        assert_eq!(format_bytes(1024), "1.0 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 + 512 * 1024), "5.5 MiB");
+    }
+}
@@ -0,0 +1,472 @@
+//! Store of submitted answers.
+//! `cargo solve <day> --submit <part>` records the outcome of every submission in `data/answers.json`,
+//! so answers that were accepted by the website can be used to check solutions later on.
+//! The cooldown after a wrong answer is stored as well, so no answer is submitted before it has passed.
+
+use std::{collections::HashMap, fs, io, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::error::Error;
+use crate::template::{clock::Clock, Day};
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Outcome of a submission, as reported by aoc-cli.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionResult {
+    Correct,
+    Incorrect,
+    /// The answer was submitted during the cooldown after a wrong answer.
+    TooRecent,
+    /// The part was solved before, the answer was not checked.
+    AlreadyCompleted,
+    Unknown,
+}
+
+impl SubmissionResult {
+    /// Reads the result from the output of `aoc submit`.
+    pub fn from_output(output: &str) -> Self {
+        if output.contains("That's the right answer") {
+            SubmissionResult::Correct
+        } else if output.contains("That's not the right answer") {
+            SubmissionResult::Incorrect
+        } else if output.contains("You gave an answer too recently") {
+            SubmissionResult::TooRecent
+        } else if output.contains("You don't seem to be solving the right level") {
+            SubmissionResult::AlreadyCompleted
+        } else {
+            SubmissionResult::Unknown
+        }
+    }
+}
+
+/// Reads the number of seconds to wait before the next submission from the output of `aoc submit`.
+/// The website either asks to "wait one minute before trying again" or reports "1m 30s left to wait".
+pub fn parse_cooldown(output: &str) -> Option<u64> {
+    let output = output.to_lowercase();
+
+    if let Some(end) = output.find(" left to wait") {
+        let start = output[..end].rfind("you have ")? + "you have ".len();
+        return output[start..end]
+            .split_whitespace()
+            .map(|token| {
+                let (amount, unit) = token.split_at(token.len().checked_sub(1)?);
+                let amount: u64 = amount.parse().ok()?;
+                match unit {
+                    "h" => Some(amount * 3600),
+                    "m" => Some(amount * 60),
+                    "s" => Some(amount),
+                    _ => None,
+                }
+            })
+            .sum();
+    }
+
+    let start = output.find("please wait ")? + "please wait ".len();
+    let mut words = output[start..].split_whitespace();
+    let amount: u64 = match words.next()? {
+        "one" => 1,
+        amount => amount.parse().ok()?,
+    };
+
+    match words.next()? {
+        "second" | "seconds" => Some(amount),
+        "minute" | "minutes" => Some(amount * 60),
+        "hour" | "hours" => Some(amount * 3600),
+        _ => None,
+    }
+}
+
+/// Submitted answers of one part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartAnswers {
+    /// The answer accepted by the website.
+    pub answer: Option<String>,
+    /// Answers rejected by the website, in order of submission.
+    pub rejected: Vec<String>,
+    /// Unix timestamp until which the website does not accept answers.
+    pub cooldown_until: Option<u64>,
+}
+
+/// Submitted answers of both parts of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayAnswers {
+    pub day: Day,
+    pub part_1: PartAnswers,
+    pub part_2: PartAnswers,
+}
+
+impl DayAnswers {
+    pub fn part(&self, part: u8) -> &PartAnswers {
+        if part == 1 {
+            &self.part_1
+        } else {
+            &self.part_2
+        }
+    }
+
+    fn part_mut(&mut self, part: u8) -> &mut PartAnswers {
+        if part == 1 {
+            &mut self.part_1
+        } else {
+            &mut self.part_2
+        }
+    }
+}
+
+/// Submitted answers of a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub data: Vec<DayAnswers>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    /// Fails if the file cannot be read or parsed, so it is not overwritten with a single answer.
+    pub fn read_from_file() -> Result<Self, Error> {
+        Self::read_file_if_present().map(Option::unwrap_or_default)
+    }
+
+    /// Like [`Answers::read_from_file`], but returns `None` if there is no answers file.
+    pub fn read_file_if_present() -> Result<Option<Self>, Error> {
+        let context = format!("Failed to read submitted answers from \"{ANSWERS_FILE_PATH}\"");
+
+        match fs::read_to_string(ANSWERS_FILE_PATH) {
+            Ok(contents) => Answers::from_str(&contents).map(Some).map_err(|e| {
+                Error::io(
+                    format!("{context}, fix or remove the file"),
+                    io::Error::new(io::ErrorKind::InvalidData, e),
+                )
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(Error::io(context, e)),
+        }
+    }
+
+    pub fn day(&self, day: Day) -> Option<&DayAnswers> {
+        self.data.iter().find(|x| x.day == day)
+    }
+
+    /// The verified answer of a part, if it was accepted by the website.
+    pub fn verified(&self, day: Day, part: u8) -> Option<&str> {
+        self.day(day)?.part(part).answer.as_deref()
+    }
+
+    /// Seconds left until answers for a part are accepted again, if it is on cooldown at the time of `clock`.
+    pub fn cooldown_remaining(&self, day: Day, part: u8, clock: &dyn Clock) -> Option<u64> {
+        let until = self.day(day)?.part(part).cooldown_until?;
+        until
+            .checked_sub(clock.unix_seconds())
+            .filter(|remaining| *remaining > 0)
+    }
+
+    /// Sets the cooldown of a part. Returns `true` if the answers changed.
+    pub fn set_cooldown(&mut self, day: Day, part: u8, until: Option<u64>) -> bool {
+        let part = self.part_mut(day, part);
+        let changed = part.cooldown_until != until;
+        part.cooldown_until = until;
+        changed
+    }
+
+    /// Records the result of submitting `answer`. Returns `true` if the answers changed.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str, result: SubmissionResult) -> bool {
+        let part = self.part_mut(day, part);
+
+        match result {
+            SubmissionResult::Correct if part.answer.as_deref() != Some(answer) => {
+                part.answer = Some(answer.to_string());
+                true
+            }
+            SubmissionResult::Incorrect if !part.rejected.iter().any(|x| x == answer) => {
+                part.rejected.push(answer.to_string());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn part_mut(&mut self, day: Day, part: u8) -> &mut PartAnswers {
+        let index = match self.data.iter().position(|x| x.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(DayAnswers {
+                    day,
+                    part_1: PartAnswers::default(),
+                    part_2: PartAnswers::default(),
+                });
+                self.data.sort_unstable_by_key(|x| x.day);
+                self.data.iter().position(|x| x.day == day).unwrap()
+            }
+        };
+
+        self.data[index].part_mut(part)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(DayAnswers::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&DayAnswers> for JsonValue {
+    fn from(value: &DayAnswers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part_1".into(), JsonValue::from(&value.part_1));
+        map.insert("part_2".into(), JsonValue::from(&value.part_2));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for DayAnswers {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answers to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answers.day to be a Day struct.")?;
+
+        let part = |key: &str| {
+            json.get(key)
+                .map(PartAnswers::try_from)
+                .transpose()
+                .map(Option::unwrap_or_default)
+        };
+
+        Ok(DayAnswers {
+            day,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+        })
+    }
+}
+
+impl From<&PartAnswers> for JsonValue {
+    fn from(value: &PartAnswers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "rejected".into(),
+            JsonValue::Array(
+                value
+                    .rejected
+                    .iter()
+                    .cloned()
+                    .map(JsonValue::String)
+                    .collect(),
+            ),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "cooldown_until".into(),
+            value
+                .cooldown_until
+                .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartAnswers {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part answers to be a JSON object.")?;
+
+        let answer = json.get("answer").and_then(|v| v.get::<String>()).cloned();
+
+        let rejected = json
+            .get("rejected")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .map(|v| {
+                v.iter()
+                    .filter_map(|x| x.get::<String>())
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let cooldown_until = json
+            .get("cooldown_until")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64);
+
+        Ok(PartAnswers {
+            answer,
+            rejected,
+            cooldown_until,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use tinyjson::JsonValue;
+
+    use super::{parse_cooldown, Answers, SubmissionResult};
+    use crate::{day, template::clock::FixedClock};
+
+    #[test]
+    fn parses_submission_results() {
+        assert_eq!(
+            SubmissionResult::from_output("That's the right answer! You are one gold star closer."),
+            SubmissionResult::Correct
+        );
+        assert_eq!(
+            SubmissionResult::from_output("That's not the right answer; your answer is too low."),
+            SubmissionResult::Incorrect
+        );
+        assert_eq!(
+            SubmissionResult::from_output(
+                "You gave an answer too recently; you have 42s left to wait."
+            ),
+            SubmissionResult::TooRecent
+        );
+        assert_eq!(
+            SubmissionResult::from_output("You don't seem to be solving the right level."),
+            SubmissionResult::AlreadyCompleted
+        );
+        assert_eq!(SubmissionResult::from_output(""), SubmissionResult::Unknown);
+    }
+
+    #[test]
+    fn parses_cooldowns() {
+        assert_eq!(
+            parse_cooldown(
+                "That's not the right answer. Please wait one minute before trying again."
+            ),
+            Some(60)
+        );
+        assert_eq!(
+            parse_cooldown(
+                "That's not the right answer. please wait 5 minutes before trying again."
+            ),
+            Some(300)
+        );
+        assert_eq!(
+            parse_cooldown("You gave an answer too recently. You have 1m 30s left to wait."),
+            Some(90)
+        );
+        assert_eq!(parse_cooldown("You have 42s left to wait."), Some(42));
+        assert_eq!(parse_cooldown("That's the right answer!"), None);
+    }
+
+    #[test]
+    fn tracks_cooldowns() {
+        let mut answers = Answers::default();
+        assert_eq!(answers.set_cooldown(day!(4), 1, Some(1_000)), true);
+        assert_eq!(answers.set_cooldown(day!(4), 1, Some(1_000)), false);
+
+        let clock = FixedClock::from_unix_seconds(940);
+        assert_eq!(answers.cooldown_remaining(day!(4), 1, &clock), Some(60));
+        assert_eq!(answers.cooldown_remaining(day!(4), 2, &clock), None);
+
+        let clock = FixedClock::from_unix_seconds(1_000);
+        assert_eq!(answers.cooldown_remaining(day!(4), 1, &clock), None);
+    }
+
+    #[test]
+    fn records_submissions() {
+        let mut answers = Answers::default();
+        assert_eq!(
+            answers.record(day!(3), 1, "10", SubmissionResult::Incorrect),
+            true
+        );
+        assert_eq!(
+            answers.record(day!(3), 1, "10", SubmissionResult::Incorrect),
+            false
+        );
+        assert_eq!(
+            answers.record(day!(3), 1, "12", SubmissionResult::TooRecent),
+            false
+        );
+        assert_eq!(
+            answers.record(day!(3), 1, "11", SubmissionResult::Correct),
+            true
+        );
+        assert_eq!(
+            answers.record(day!(1), 2, "31", SubmissionResult::Correct),
+            true
+        );
+
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.verified(day!(3), 1), Some("11"));
+        assert_eq!(answers.verified(day!(3), 2), None);
+        assert_eq!(answers.day(day!(3)).unwrap().part_1.rejected, vec!["10"]);
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let mut answers = Answers::default();
+        answers.record(day!(2), 1, "2", SubmissionResult::Correct);
+        answers.record(day!(2), 2, "3", SubmissionResult::Incorrect);
+        answers.set_cooldown(day!(2), 2, Some(1_733_029_260));
+
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::from_str(&json), Ok(answers));
+    }
+}
@@ -0,0 +1,258 @@
+//! Detection of puzzle inputs and descriptions in files that are about to be committed, see `cargo audit-data`.
+//! Files are flagged by their path, and by fingerprints of the inputs and puzzles in the local `data/` directory,
+//! so copies of them are found as well, e.g. an input pasted into a test.
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fmt::Display,
+    fs,
+    hash::{Hash, Hasher},
+    path::Path,
+};
+
+use crate::template::inputs::{self, KEY_FILE};
+use crate::template::{all_days, puzzle, Day};
+
+/// Number of consecutive lines of an input that make up a fingerprint.
+const INPUT_WINDOW: usize = 5;
+/// Windows of an input that are shorter than this are too generic to identify it, e.g. empty lines.
+const MIN_WINDOW_LENGTH: usize = 40;
+/// Lines of an input that are at least this long identify it on their own.
+const MIN_LINE_LENGTH: usize = 100;
+/// Paragraphs of a puzzle that are shorter than this are too generic to identify it, e.g. headings or examples.
+const MIN_PARAGRAPH_LENGTH: usize = 200;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reason {
+    InputPath,
+    PuzzlePath,
+    KeyFile,
+    InputContent(Day),
+    PuzzleContent(Day),
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reason::InputPath => write!(f, "is a puzzle input"),
+            Reason::PuzzlePath => write!(f, "is a puzzle description"),
+            Reason::KeyFile => write!(f, "is the key of the encrypted inputs"),
+            Reason::InputContent(day) => write!(f, "contains the input of day {day}"),
+            Reason::PuzzleContent(day) => write!(f, "contains the puzzle description of day {day}"),
+        }
+    }
+}
+
+/// Flags files that must not be committed by their path. Encrypted inputs and the `.keep` files are allowed.
+pub fn check_path(path: &str) -> Option<Reason> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+
+    if path == KEY_FILE {
+        Some(Reason::KeyFile)
+    } else if path.starts_with("data/inputs/") && name != ".keep" && !name.ends_with(".enc") {
+        Some(Reason::InputPath)
+    } else if path.starts_with("data/puzzles/") && name != ".keep" {
+        Some(Reason::PuzzlePath)
+    } else {
+        None
+    }
+}
+
+/// Fingerprints of known inputs and puzzles.
+#[derive(Debug, Default)]
+pub struct Fingerprints(HashMap<u64, Reason>);
+
+impl Fingerprints {
+    /// Fingerprints the inputs and puzzles in `data/`. Encrypted inputs are included if the key is available.
+    pub fn from_project() -> Self {
+        let mut fingerprints = Self::default();
+
+        for day in all_days() {
+            let input_path = format!("data/inputs/{day}.txt");
+            if inputs::is_readable(Path::new(&input_path)) {
+                if let Ok(input) = inputs::read(Path::new(&input_path)) {
+                    fingerprints.add_input(day, &input);
+                }
+            }
+            if let Ok(puzzle) = fs::read_to_string(puzzle::path(day)) {
+                fingerprints.add_puzzle(day, &puzzle);
+            }
+        }
+
+        fingerprints
+    }
+
+    pub fn add_input(&mut self, day: Day, input: &str) {
+        for hash in input_windows(input) {
+            self.0.insert(hash, Reason::InputContent(day));
+        }
+    }
+
+    /// Only the prose of a puzzle is fingerprinted, its examples are committed to `data/examples/`.
+    pub fn add_puzzle(&mut self, day: Day, puzzle: &str) {
+        for hash in paragraphs(&without_code_blocks(puzzle)) {
+            self.0.insert(hash, Reason::PuzzleContent(day));
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns why the contents of a file must not be committed, if they contain a known input or puzzle.
+    pub fn check(&self, contents: &str) -> Option<Reason> {
+        input_windows(contents)
+            .chain(paragraphs(contents))
+            .find_map(|hash| self.0.get(&hash).copied())
+    }
+}
+
+fn hash(value: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Hashes every window of consecutive lines, ignoring indentation and line endings, and every long line.
+/// Inputs with fewer lines than a window are hashed as a whole.
+fn input_windows(text: &str) -> impl Iterator<Item = u64> {
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    let size = INPUT_WINDOW.min(lines.len()).max(1);
+
+    let windows = lines
+        .windows(size)
+        .map(|window| window.join("\n"))
+        .filter(|window| window.len() >= MIN_WINDOW_LENGTH);
+    // inputs with a single long line, e.g. day 3 of 2024, are found by that line.
+    let long_lines = lines
+        .iter()
+        .filter(|line| line.len() >= MIN_LINE_LENGTH)
+        .map(ToString::to_string);
+
+    windows
+        .chain(long_lines)
+        .map(|value| hash(&value))
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+/// Hashes every long paragraph with its whitespace collapsed, so rewrapped text is found as well.
+fn paragraphs(text: &str) -> impl Iterator<Item = u64> {
+    text.replace("\r\n", "\n")
+        .split("\n\n")
+        .map(|paragraph| paragraph.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|paragraph| paragraph.len() >= MIN_PARAGRAPH_LENGTH)
+        .map(|paragraph| hash(&paragraph))
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+fn without_code_blocks(text: &str) -> String {
+    let mut in_code_block = false;
+
+    text.lines()
+        .filter(|line| {
+            let is_fence = line.trim_start().starts_with("```");
+            in_code_block ^= is_fence;
+            !in_code_block && !is_fence
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A file that must not be committed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finding {
+    pub path: String,
+    pub reason: Reason,
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\"{}\" {}", self.path, self.reason)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_path, Fingerprints, Reason};
+    use crate::day;
+
+    const INPUT: &str = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+
+    const PUZZLE: &str = "## --- Day 2: Red-Nosed Reports ---
+
+Fortunately, the first location The Historians want to search isn't a long walk from the Chief Historian's office.
+While the Red-Nosed Reindeer nuclear fusion/fission plant appears to contain no sign of the Chief Historian, the engineers
+there run up to you as soon as they see you.
+
+```
+7 6 4 2 1
+```
+";
+
+    #[test]
+    fn checks_paths() {
+        assert_eq!(check_path("data/inputs/01.txt"), Some(Reason::InputPath));
+        assert_eq!(check_path("data/puzzles/01.md"), Some(Reason::PuzzlePath));
+        assert_eq!(check_path(".aoc-input-key"), Some(Reason::KeyFile));
+        assert_eq!(check_path("data/inputs/01.txt.enc"), None);
+        assert_eq!(check_path("data/inputs/.keep"), None);
+        assert_eq!(check_path("data/examples/01.txt"), None);
+        assert_eq!(check_path("src/bin/01.rs"), None);
+    }
+
+    #[test]
+    fn finds_copies_of_inputs() {
+        let mut fingerprints = Fingerprints::default();
+        fingerprints.add_input(day!(2), INPUT);
+
+        assert_eq!(
+            fingerprints.check(INPUT),
+            Some(Reason::InputContent(day!(2)))
+        );
+        let pasted = format!(
+            "const INPUT: &str = \"\n{}\";\n",
+            INPUT.replace('\n', "\r\n    ")
+        );
+        assert_eq!(
+            fingerprints.check(&pasted),
+            Some(Reason::InputContent(day!(2)))
+        );
+        let long_line = "mul(2,4)don't()".repeat(10);
+        fingerprints.add_input(day!(3), &long_line);
+        assert_eq!(
+            fingerprints.check(&format!("const INPUT: &str = r\"\n{long_line}\n\";\n")),
+            Some(Reason::InputContent(day!(3)))
+        );
+        // a few lines, e.g. an example, are not enough.
+        assert_eq!(fingerprints.check("7 6 4 2 1\n1 2 7 8 9\n"), None);
+        assert_eq!(fingerprints.check("fn main() {}\n"), None);
+    }
+
+    #[test]
+    fn finds_copies_of_puzzles() {
+        let mut fingerprints = Fingerprints::default();
+        fingerprints.add_puzzle(day!(2), PUZZLE);
+
+        let rewrapped = PUZZLE.replace("office.\n", "office. ");
+        assert_eq!(
+            fingerprints.check(&rewrapped),
+            Some(Reason::PuzzleContent(day!(2)))
+        );
+        // the examples of the puzzle can be committed.
+        let example = "7 6 4 2 1 1 2 7 8 9 9 7 6 2 1 ".repeat(10);
+        fingerprints.add_puzzle(day!(2), &format!("```\n{example}\n```\n"));
+        assert_eq!(fingerprints.check(&example), None);
+        assert_eq!(
+            fingerprints.check("## --- Day 2: Red-Nosed Reports ---\n"),
+            None
+        );
+    }
+}
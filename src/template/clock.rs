@@ -0,0 +1,247 @@
+//! Source of the current time.
+//! Commands that depend on the date read it from a [`Clock`], so their logic can be tested with a [`FixedClock`].
+//! Setting `AOC_NOW` to a timestamp makes the commands start at that time instead of the system time,
+//! e.g. `AOC_NOW=2024-11-30T23:59:50 cargo today --wait`.
+
+use std::{
+    env,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Offset of the Advent of Code server timezone (UTC-5) in seconds.
+pub const SERVER_UTC_OFFSET_SECS: i64 = -5 * 3600;
+
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+
+    /// The current time as seconds since the unix epoch.
+    fn unix_seconds(&self) -> u64 {
+        self.now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs())
+    }
+}
+
+/// The system time.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that is stopped at a point in time.
+pub struct FixedClock(pub SystemTime);
+
+impl FixedClock {
+    pub fn from_unix_seconds(seconds: u64) -> Self {
+        Self(UNIX_EPOCH + Duration::from_secs(seconds))
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+/// A clock that started at a point in time and runs at the speed of the system time.
+pub struct OffsetClock {
+    start: SystemTime,
+    started_at: Instant,
+}
+
+impl OffsetClock {
+    pub fn new(start: SystemTime) -> Self {
+        Self {
+            start,
+            started_at: Instant::now(),
+        }
+    }
+}
+
+impl Clock for OffsetClock {
+    fn now(&self) -> SystemTime {
+        self.start + self.started_at.elapsed()
+    }
+}
+
+/// Returns the clock commands should use: an [`OffsetClock`] starting at `AOC_NOW` if set, the system time otherwise.
+pub fn from_env() -> Box<dyn Clock> {
+    match env::var("AOC_NOW") {
+        Ok(value) if !value.is_empty() => match parse_timestamp(&value) {
+            Ok(start) => Box::new(OffsetClock::new(start)),
+            Err(e) => {
+                eprintln!("Ignoring AOC_NOW: {e}");
+                Box::new(SystemClock)
+            }
+        },
+        _ => Box::new(SystemClock),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses a timestamp as either seconds since the unix epoch or `YYYY-MM-DDTHH:MM[:SS]`, followed by
+/// an optional `Z` or `±HH:MM` offset. Timestamps without an offset are read in the server timezone (UTC-5).
+pub fn parse_timestamp(s: &str) -> Result<SystemTime, String> {
+    let s = s.trim();
+
+    if let Ok(seconds) = s.parse::<u64>() {
+        return Ok(UNIX_EPOCH + Duration::from_secs(seconds));
+    }
+
+    let invalid = || format!("expected a unix timestamp or `YYYY-MM-DDTHH:MM:SS`, found `{s}`.");
+
+    let (date, time) = s.split_once(['T', ' ']).ok_or_else(invalid)?;
+
+    let (time, offset) = if let Some(time) = time.strip_suffix('Z') {
+        (time, 0)
+    } else if let Some(index) = time.rfind(['+', '-']) {
+        let (time, offset) = time.split_at(index);
+        let sign = if offset.starts_with('-') { -1 } else { 1 };
+        let (hours, minutes) = offset[1..].split_once(':').ok_or_else(invalid)?;
+        let hours: i64 = hours.parse().map_err(|_| invalid())?;
+        let minutes: i64 = minutes.parse().map_err(|_| invalid())?;
+        (time, sign * (hours * 3600 + minutes * 60))
+    } else {
+        (time, SERVER_UTC_OFFSET_SECS)
+    };
+
+    let date: Vec<u64> = date
+        .split('-')
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map_err(|_| invalid())?;
+    let time: Vec<u64> = time
+        .split(':')
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map_err(|_| invalid())?;
+
+    let ([year, month, day], [hours, minutes, seconds @ ..]) = (date.as_slice(), time.as_slice())
+    else {
+        return Err(invalid());
+    };
+
+    let seconds = match seconds {
+        [] => 0,
+        [seconds] => *seconds,
+        _ => return Err(invalid()),
+    };
+    let valid = (1970..10_000).contains(year)
+        && (1..=12).contains(month)
+        && (1..=31).contains(day)
+        && *hours < 24
+        && *minutes < 60
+        && seconds < 60;
+
+    if !valid {
+        return Err(invalid());
+    }
+
+    // Dates past the end of their month, e.g. `2024-02-31`, would roll over into the next month.
+    let days = days_from_civil(*year, *month, *day);
+    if civil_from_days(days) != (*year, *month, *day) {
+        return Err(invalid());
+    }
+
+    let local = days * 86_400 + hours * 3600 + minutes * 60 + seconds;
+    let utc = i64::try_from(local).map_err(|_| invalid())? - offset;
+
+    u64::try_from(utc)
+        .map(|utc| UNIX_EPOCH + Duration::from_secs(utc))
+        .map_err(|_| invalid())
+}
+
+/// days-from-civil, see: https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+pub(crate) fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let yoe = year - era * 400;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Unix timestamp of midnight of a date in the server timezone, e.g. when the puzzle of that day unlocks.
+pub(crate) fn server_midnight(year: u64, month: u64, day: u64) -> u64 {
+    (days_from_civil(year, month, day) * 86_400).saturating_add_signed(-SERVER_UTC_OFFSET_SECS)
+}
+
+/// civil-from-days, the inverse of [`days_from_civil`]. Returns the year, month and day.
+/// See: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+pub(crate) fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (yoe + era * 400 + u64::from(month <= 2), month, day)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{
+        civil_from_days, days_from_civil, parse_timestamp, Clock, FixedClock, OffsetClock,
+    };
+
+    #[test]
+    fn parses_timestamps() {
+        let at = |seconds: u64| Ok(UNIX_EPOCH + Duration::from_secs(seconds));
+
+        assert_eq!(parse_timestamp("1733029200"), at(1_733_029_200));
+        assert_eq!(parse_timestamp("2024-12-01T05:00:00Z"), at(1_733_029_200));
+        assert_eq!(
+            parse_timestamp("2024-12-01T00:00:00-05:00"),
+            at(1_733_029_200)
+        );
+        assert_eq!(parse_timestamp("2024-12-01T06:00+01:00"), at(1_733_029_200));
+        // without an offset, timestamps are in the server timezone.
+        assert_eq!(parse_timestamp("2024-12-01 00:00"), at(1_733_029_200));
+        assert_eq!(parse_timestamp("2024-11-30T23:59:59"), at(1_733_029_199));
+    }
+
+    #[test]
+    fn rejects_invalid_timestamps() {
+        assert!(parse_timestamp("").is_err());
+        assert!(parse_timestamp("2024-12-01").is_err());
+        assert!(parse_timestamp("2024-13-01T00:00").is_err());
+        assert!(parse_timestamp("2024-12-01T24:00").is_err());
+        assert!(parse_timestamp("2024-12-01T00:00+5").is_err());
+        assert!(parse_timestamp("2024-02-31T00:00").is_err());
+        assert!(parse_timestamp("2023-02-29T00:00").is_err());
+        assert!(parse_timestamp("2024-04-31T00:00").is_err());
+        assert!(parse_timestamp("2024-02-29T00:00").is_ok());
+    }
+
+    #[test]
+    fn converts_civil_dates() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        for (year, month, day) in [(2024, 2, 29), (2024, 12, 1), (2000, 3, 1), (2023, 12, 31)] {
+            assert_eq!(
+                civil_from_days(days_from_civil(year, month, day)),
+                (year, month, day)
+            );
+        }
+    }
+
+    #[test]
+    fn clocks() {
+        let clock = FixedClock::from_unix_seconds(1_733_029_200);
+        assert_eq!(clock.unix_seconds(), 1_733_029_200);
+
+        let clock = OffsetClock::new(UNIX_EPOCH + Duration::from_secs(1_733_029_200));
+        assert!(clock.unix_seconds() >= 1_733_029_200);
+        assert!(clock.unix_seconds() < 1_733_029_260);
+    }
+}
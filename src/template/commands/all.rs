@@ -1,5 +1,7 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::error::Error;
+use crate::template::{allocations::AllocTracking, run_multi::run_multi, DaySet};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(days: &DaySet, is_release: bool) -> Result<(), Error> {
+    run_multi(days.as_hash_set(), is_release, false, AllocTracking::Off)?;
+    Ok(())
 }
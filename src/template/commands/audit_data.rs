@@ -0,0 +1,92 @@
+use std::process::Command;
+
+use crate::template::audit::{check_path, Finding, Fingerprints};
+use crate::template::error::Error;
+
+/// Runs git and returns its output, e.g. a list of files.
+fn git(args: &[&str]) -> Result<Vec<u8>, Error> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| Error::io("Failed to run git", e))?;
+
+    if !output.status.success() {
+        return Err(Error::Usage(format!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(output.stdout)
+}
+
+/// Contents of a file in the index, `None` for binary files such as encrypted inputs.
+fn staged_contents(path: &str) -> Option<String> {
+    git(&["show", &format!(":{path}")])
+        .ok()
+        .and_then(|contents| String::from_utf8(contents).ok())
+}
+
+/// Checks the staged files, or every tracked file with `all`, for puzzle inputs and descriptions.
+/// Fails with [`Error::Data`] if any are found, so it can be used as a pre-commit hook, see `cargo hooks install`.
+pub fn handle(all: bool) -> Result<(), Error> {
+    let files = if all {
+        git(&["ls-files", "-z"])?
+    } else {
+        git(&[
+            "diff",
+            "--cached",
+            "--name-only",
+            "-z",
+            "--diff-filter=ACMR",
+        ])?
+    };
+    let files: Vec<String> = files
+        .split(|byte| *byte == 0)
+        .filter(|path| !path.is_empty())
+        .map(|path| String::from_utf8_lossy(path).into_owned())
+        .collect();
+
+    let fingerprints = Fingerprints::from_project();
+    let findings: Vec<Finding> = files
+        .iter()
+        .filter_map(|path| {
+            let reason = check_path(path).or_else(|| {
+                staged_contents(path).and_then(|contents| fingerprints.check(&contents))
+            })?;
+            Some(Finding {
+                path: path.clone(),
+                reason,
+            })
+        })
+        .collect();
+
+    if findings.is_empty() {
+        println!(
+            "No puzzle inputs or descriptions found in {} {} {}.",
+            files.len(),
+            if all { "tracked" } else { "staged" },
+            if files.len() == 1 { "file" } else { "files" }
+        );
+        if fingerprints.is_empty() {
+            println!("There are no local inputs or puzzles to compare the files to, only their paths were checked.");
+        }
+        return Ok(());
+    }
+
+    for finding in &findings {
+        eprintln!("✖ {finding}");
+    }
+
+    let fix = if all {
+        "Remove them from git with `git rm --cached <file>`."
+    } else {
+        "Unstage them with `git restore --staged <file>`."
+    };
+    Err(Error::Data(format!(
+        "Puzzle inputs and descriptions must not be published, found {} {}. {fix}",
+        findings.len(),
+        if findings.len() == 1 { "file" } else { "files" }
+    )))
+}
@@ -0,0 +1,31 @@
+use std::process::{Command, Stdio};
+
+use crate::template::error::Error;
+use crate::template::Day;
+
+pub fn handle(day: Day) -> Result<(), Error> {
+    let cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+        "--bench-compare".to_string(),
+    ];
+
+    let status = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .map_err(|e| Error::io("Failed to run cargo", e))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::Solution(format!(
+            "Comparing the implementations of day {day} failed."
+        )))
+    }
+}
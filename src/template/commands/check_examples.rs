@@ -0,0 +1,55 @@
+use std::{
+    fs,
+    io::{self, Write},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::error::Error;
+use crate::template::example_tests::{self, DayResult};
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::DaySet;
+
+/// Runs the example tests of every day with a solution file and prints which parts pass, as a grid or as JSON.
+pub fn handle(days: &DaySet, json: bool) -> Result<(), Error> {
+    let mut results: Vec<DayResult> = vec![];
+
+    for day in days
+        .iter()
+        .filter(|day| fs::metadata(get_path_for_bin(*day)).is_ok())
+    {
+        if !json {
+            print!("Testing day {day}...\r");
+            let _ = io::stdout().flush();
+        }
+        results.push(example_tests::run(day).map_err(|e| Error::io("Failed to run cargo", e))?);
+    }
+
+    if json {
+        let value = JsonValue::Array(results.iter().map(JsonValue::from).collect());
+        let s = value
+            .format()
+            .map_err(|e| Error::io("Failed to serialize results", io::Error::other(e)))?;
+        println!("{s}");
+    } else if results.is_empty() {
+        println!("No solutions to test.");
+    } else {
+        println!("{}", example_tests::format_grid(&results));
+    }
+
+    let failed: Vec<String> = results
+        .iter()
+        .filter(|r| !r.passed())
+        .map(|r| r.day.to_string())
+        .collect();
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Solution(format!(
+            "Examples of day {} fail, run e.g. `cargo test --bin {}` for details.",
+            failed.join(", "),
+            failed[0]
+        )))
+    }
+}
@@ -0,0 +1,106 @@
+use std::{
+    fs,
+    io::{self, Write},
+};
+
+use crate::template::error::Error;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::Day;
+use crate::template::{examples, puzzle};
+
+pub fn handle(day: Day, yes: bool) -> Result<(), Error> {
+    let puzzle_path = puzzle::path(day);
+
+    let Ok(puzzle) = fs::read_to_string(&puzzle_path) else {
+        return Err(Error::NotFound(format!(
+            "Puzzle \"{puzzle_path}\" not found. Download it with `cargo download {day}` first."
+        )));
+    };
+
+    let examples = examples::extract(&puzzle);
+
+    match &examples.part_1.input {
+        Some(example) => write_example(&format!("data/examples/{day}.txt"), example, yes)?,
+        None => println!("No example input found in \"{puzzle_path}\"."),
+    }
+
+    if let Some(example) = examples.separate_part_2_input() {
+        write_example(&format!("data/examples/{day}-2.txt"), example, yes)?;
+    }
+
+    for (part, example) in [(1, &examples.part_1), (2, &examples.part_2)] {
+        match &example.answer {
+            Some(answer) => println!("Part {part}: expected example answer {answer}"),
+            None => println!("Part {part}: no expected example answer found"),
+        }
+    }
+
+    let module_path = get_path_for_bin(day);
+    let Ok(source) = fs::read_to_string(&module_path) else {
+        return Ok(());
+    };
+
+    let (mut updated, filled) = examples::fill_assertions(
+        &source,
+        [
+            examples.part_1.answer.as_deref(),
+            examples.part_2.answer.as_deref(),
+        ],
+    );
+
+    if examples.separate_part_2_input().is_some() {
+        updated = examples::use_part_two_example(&updated);
+    }
+
+    if updated == source {
+        return Ok(());
+    }
+
+    fs::write(&module_path, updated)
+        .map_err(|e| Error::io(format!("Failed to update \"{module_path}\""), e))?;
+
+    if filled.is_empty() {
+        println!("Updated example files in \"{module_path}\"");
+    } else {
+        let parts: Vec<String> = filled.iter().map(ToString::to_string).collect();
+        println!(
+            "Filled in the expected answers of part {} in \"{module_path}\"",
+            parts.join(" and ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Writes an example file, asking before an existing file with other contents is replaced.
+fn write_example(path: &str, example: &str, yes: bool) -> Result<(), Error> {
+    match fs::read_to_string(path) {
+        Ok(existing) if existing == example => {
+            println!("Example file \"{path}\" is up to date");
+            return Ok(());
+        }
+        Ok(existing)
+            if !existing.trim().is_empty()
+                && !yes
+                && !confirm(&format!(
+                    "Overwrite \"{path}\" with the example of the puzzle?"
+                )) =>
+        {
+            println!("Kept existing example file \"{path}\"");
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    fs::write(path, example).map_err(|e| Error::io("Failed to write example file", e))?;
+    println!("Wrote example file \"{path}\"");
+    Ok(())
+}
+
+fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = io::stdout().flush();
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).is_ok() && answer.trim().eq_ignore_ascii_case("y")
+}
@@ -0,0 +1,39 @@
+use std::process::{Command, Stdio};
+
+use crate::template::error::Error;
+use crate::template::Day;
+
+pub fn handle(day: Day, iterations: Option<u64>, seed: Option<u64>) -> Result<(), Error> {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+        "--fuzz-check".to_string(),
+    ];
+
+    if let Some(iterations) = iterations {
+        cmd_args.push("--iterations".to_string());
+        cmd_args.push(iterations.to_string());
+    }
+
+    if let Some(seed) = seed {
+        cmd_args.push("--seed".to_string());
+        cmd_args.push(seed.to_string());
+    }
+
+    let status = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .map_err(|e| Error::io("Failed to run cargo", e))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::Solution(format!("Fuzz check of day {day} failed.")))
+    }
+}
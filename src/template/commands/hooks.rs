@@ -0,0 +1,78 @@
+use std::{fs, path::PathBuf, process::Command};
+
+use crate::template::error::Error;
+
+/// Marks hooks written by this command, other hooks are never replaced or removed.
+const MARKER: &str = "# installed by `cargo hooks install`";
+
+fn hook_path() -> Result<PathBuf, Error> {
+    // respects `core.hooksPath` and worktrees.
+    let output = Command::new("git")
+        .args(["rev-parse", "--git-path", "hooks/pre-commit"])
+        .output()
+        .map_err(|e| Error::io("Failed to run git", e))?;
+
+    if !output.status.success() {
+        return Err(Error::Usage("Not inside a git repository.".into()));
+    }
+
+    Ok(PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    ))
+}
+
+fn is_own_hook(contents: &str) -> bool {
+    contents.contains(MARKER)
+}
+
+/// Installs a pre-commit hook that runs `cargo audit-data`.
+pub fn handle_install() -> Result<(), Error> {
+    let path = hook_path()?;
+
+    if fs::read_to_string(&path).is_ok_and(|contents| !is_own_hook(&contents)) {
+        return Err(Error::Usage(format!(
+            "\"{}\" already exists. Add `cargo audit-data` to it instead.",
+            path.display()
+        )));
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| Error::io("Failed to create hooks directory", e))?;
+    }
+    fs::write(
+        &path,
+        format!("#!/bin/sh\n{MARKER}\nexec cargo audit-data\n"),
+    )
+    .map_err(|e| Error::io("Failed to write pre-commit hook", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+            .map_err(|e| Error::io("Failed to make pre-commit hook executable", e))?;
+    }
+
+    println!(
+        "Installed pre-commit hook \"{}\". Commits with puzzle inputs or descriptions are refused now.",
+        path.display()
+    );
+    Ok(())
+}
+
+/// Removes the pre-commit hook installed by [`handle_install`].
+pub fn handle_uninstall() -> Result<(), Error> {
+    let path = hook_path()?;
+
+    match fs::read_to_string(&path) {
+        Ok(contents) if is_own_hook(&contents) => {
+            fs::remove_file(&path).map_err(|e| Error::io("Failed to remove pre-commit hook", e))?;
+            println!("Removed pre-commit hook \"{}\".", path.display());
+            Ok(())
+        }
+        Ok(_) => Err(Error::Usage(format!(
+            "\"{}\" was not installed by `cargo hooks install`, it was kept.",
+            path.display()
+        ))),
+        Err(_) => Err(Error::NotFound("No pre-commit hook is installed.".into())),
+    }
+}
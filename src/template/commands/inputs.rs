@@ -0,0 +1,100 @@
+use std::{fs, path::Path};
+
+use crate::template::error::Error;
+use crate::template::inputs::{self, InputKey, KEY_ENV, KEY_FILE};
+use crate::template::{Day, DaySet};
+
+fn input_path(day: Day) -> String {
+    format!("data/inputs/{day}.txt")
+}
+
+fn load_key() -> Result<InputKey, Error> {
+    InputKey::load()?.ok_or_else(|| {
+        Error::Usage(format!(
+            "No input key found. Set {KEY_ENV} or run `cargo inputs keygen` to create \"{KEY_FILE}\"."
+        ))
+    })
+}
+
+/// Writes the encrypted copy of every input of a set of days. Inputs whose encrypted copy is up to date
+/// are skipped, so their files do not change in git.
+pub fn handle_encrypt(days: &DaySet) -> Result<(), Error> {
+    let key = load_key()?;
+
+    for day in days.iter() {
+        let path = input_path(day);
+        let encrypted_path = inputs::encrypted_path(Path::new(&path));
+        let name = format!("{day}.txt");
+
+        let input = match fs::read_to_string(&path) {
+            Ok(input) if !input.is_empty() => input,
+            _ => continue,
+        };
+
+        let up_to_date = fs::read(&encrypted_path)
+            .ok()
+            .and_then(|data| key.decrypt(&name, &data).ok())
+            .is_some_and(|decrypted| decrypted == input);
+        if up_to_date {
+            continue;
+        }
+
+        fs::write(&encrypted_path, key.encrypt(&name, &input))
+            .map_err(|e| Error::io("Failed to write encrypted input", e))?;
+        println!("Encrypted \"{path}\" to \"{}\"", encrypted_path.display());
+    }
+
+    Ok(())
+}
+
+/// Writes the input of every day of a set of days that has an encrypted copy.
+/// Existing inputs that differ from their encrypted copy are only replaced with `force`.
+pub fn handle_decrypt(days: &DaySet, force: bool) -> Result<(), Error> {
+    let key = load_key()?;
+    let mut conflicts: Vec<String> = vec![];
+
+    for day in days.iter() {
+        let path = input_path(day);
+        let encrypted_path = inputs::encrypted_path(Path::new(&path));
+
+        let Ok(data) = fs::read(&encrypted_path) else {
+            continue;
+        };
+        let input = key.decrypt(&format!("{day}.txt"), &data)?;
+
+        match fs::read_to_string(&path) {
+            Ok(existing) if existing == input => continue,
+            Ok(existing) if !existing.is_empty() && !force => {
+                conflicts.push(path);
+                continue;
+            }
+            _ => {}
+        }
+
+        fs::write(&path, input).map_err(|e| Error::io("Failed to write input", e))?;
+        println!("Decrypted \"{}\" to \"{path}\"", encrypted_path.display());
+    }
+
+    if conflicts.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Usage(format!(
+            "Kept inputs that differ from their encrypted copy: {}. Pass --force to replace them.",
+            conflicts.join(", ")
+        )))
+    }
+}
+
+/// Creates a new key in the key file. An existing key file is never replaced, its inputs could not be
+/// decrypted anymore.
+pub fn handle_keygen() -> Result<(), Error> {
+    if Path::new(KEY_FILE).exists() {
+        return Err(Error::Usage(format!("\"{KEY_FILE}\" already exists.")));
+    }
+
+    fs::write(KEY_FILE, format!("{}\n", InputKey::generate().to_hex()))
+        .map_err(|e| Error::io("Failed to write key file", e))?;
+    println!("Created key file \"{KEY_FILE}\". Keep a copy of it, e.g. as the {KEY_ENV} secret of your CI.");
+
+    Ok(())
+}
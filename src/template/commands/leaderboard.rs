@@ -0,0 +1,30 @@
+use std::env;
+
+use crate::template::error::Error;
+use crate::template::{leaderboard, Day};
+
+/// Shows a private leaderboard. Without an id, the `AOC_LEADERBOARD_ID` env var is used.
+pub fn handle(id: Option<String>, day: Option<Day>, offline: bool) -> Result<(), Error> {
+    let id = id
+        .or_else(|| env::var("AOC_LEADERBOARD_ID").ok())
+        .ok_or_else(|| {
+            Error::Usage(
+                "No leaderboard id given. Pass it as argument or set AOC_LEADERBOARD_ID.".into(),
+            )
+        })?;
+
+    if !leaderboard::is_valid_id(&id) {
+        return Err(Error::Usage(format!(
+            "Invalid leaderboard id `{id}`, expected the number at the end of the leaderboard URL."
+        )));
+    }
+
+    let year = env::var("AOC_YEAR")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .ok_or_else(|| Error::Usage("AOC_YEAR is not set to a valid year.".into()))?;
+
+    let leaderboard = leaderboard::load(year, &id, offline).map_err(Error::Fetch)?;
+    leaderboard::print(&leaderboard, day);
+    Ok(())
+}
@@ -1,6 +1,19 @@
 pub mod all;
+pub mod audit_data;
+pub mod bench_compare;
+pub mod check_examples;
 pub mod download;
+pub mod extract_examples;
+pub mod fuzz_check;
+pub mod hooks;
+pub mod inputs;
+pub mod leaderboard;
 pub mod read;
+pub mod report;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
+pub mod test;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
@@ -1,15 +1,43 @@
-use std::process;
+use std::fs;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::error::Error;
+use crate::template::{aoc_cli, markdown, puzzle, terminal, Day};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// Longest line of prose, wider text is hard to read.
+const MAX_WIDTH: usize = 100;
+
+/// Renders the local copy of the puzzle, or one of its parts. The puzzle is only downloaded if it is missing.
+pub fn handle(day: Day, part: Option<u8>) -> Result<(), Error> {
+    let path = puzzle::path(day);
+
+    if fs::metadata(&path).is_err() {
+        if aoc_cli::check().is_err() {
+            return Err(Error::NotFound(format!(
+                "Puzzle \"{path}\" not found and aoc-cli is not installed to download it. Try running \"cargo install aoc-cli\" to install it."
+            )));
+        }
+        aoc_cli::download_puzzle(day)?;
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| Error::io(format!("Failed to read puzzle \"{path}\""), e))?;
+
+    let section = match part {
+        None => Some(contents.as_str()),
+        Some(part) => puzzle::part(&contents, part),
     };
+
+    match (section, part) {
+        (Some(section), _) => {
+            let width = terminal::width().min(MAX_WIDTH);
+            terminal::page(&markdown::render(section, width));
+            Ok(())
+        }
+        (None, Some(2)) => Err(Error::NotFound(format!(
+            "Part two is not in \"{path}\". Solve part one first, or run `cargo download {day}` if you already did."
+        ))),
+        (None, _) => Err(Error::Usage(
+            "Unexpected part, expecting 1 or 2.".into(),
+        )),
+    }
 }
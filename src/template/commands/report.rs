@@ -0,0 +1,37 @@
+use std::fs;
+
+use crate::template::error::Error;
+use crate::template::history;
+use crate::template::report::{self, Format};
+use crate::template::timings::Timings;
+
+/// Renders the stored benchmarks to `output`, or to stdout if no path is passed.
+/// The format defaults to the extension of `output`, falling back to markdown for paths without one.
+pub fn handle(format: Option<Format>, output: Option<String>) -> Result<(), Error> {
+    let format = match (format, output.as_deref()) {
+        (Some(format), _) => format,
+        (None, Some(path)) => Format::from_path(path)
+            .map_err(Error::Usage)?
+            .unwrap_or(Format::Markdown),
+        (None, None) => Format::Markdown,
+    };
+
+    let timings = Timings::read_from_file()?;
+    if timings.data.is_empty() {
+        return Err(Error::NotFound(
+            "No stored benchmarks. Run `cargo time --store` first.".into(),
+        ));
+    }
+
+    let report = report::render(format, &timings, &history::read_from_file());
+
+    match output {
+        Some(path) => {
+            fs::write(&path, report).map_err(|e| Error::io("Failed to write report file", e))?;
+            println!("Created report file \"{path}\"");
+        }
+        None => print!("{report}"),
+    }
+
+    Ok(())
+}
@@ -1,13 +1,12 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
-    process,
 };
 
+use crate::template::error::Error;
+use crate::template::templates::{self, TemplateContext, DEFAULT_TEMPLATE};
 use crate::template::Day;
-
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+use crate::template::{examples, puzzle};
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -19,61 +18,87 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Creates an empty file if it does not exist yet, e.g. keeps an input downloaded before scaffolding.
+/// Returns `true` if the file was created.
+fn create_missing_file(path: &str) -> Result<bool, std::io::Error> {
+    if fs::metadata(path).is_ok() {
+        return Ok(false);
+    }
+    File::create(path).map(|_| true)
+}
+
+/// Writes an example file unless it already has contents. Returns `true` if the file was written.
+fn write_missing_example(path: &str, example: &str) -> Result<bool, std::io::Error> {
+    if fs::metadata(path).is_ok_and(|m| m.len() > 0) {
+        return Ok(false);
+    }
+    fs::write(path, example).map(|()| true)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
+pub fn handle(day: Day, overwrite: bool, template: Option<&str>) -> Result<(), Error> {
     let input_path = format!("data/inputs/{day}.txt");
     let example_path = format!("data/examples/{day}.txt");
+    let example_2_path = format!("data/examples/{day}-2.txt");
     let module_path = format!("src/bin/{day}.rs");
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
+    let template = templates::load(template.unwrap_or(DEFAULT_TEMPLATE))
+        .map_err(|e| Error::Usage(format!("Failed to load template: {e}")))?;
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+    let examples = fs::read_to_string(puzzle::path(day))
+        .map(|puzzle| examples::extract(&puzzle))
+        .unwrap_or_default();
+    let example_2 = examples.separate_part_2_input();
+
+    let mut module = templates::render(&template, &TemplateContext::from_project(day));
+    if example_2.is_some() {
+        module = examples::use_part_two_example(&module);
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    let mut file = safe_create_file(&module_path, overwrite)
+        .map_err(|e| Error::io("Failed to create module file", e))?;
+
+    file.write_all(module.as_bytes())
+        .map_err(|e| Error::io("Failed to write module contents", e))?;
+    println!("Created module file \"{}\"", &module_path);
+
+    if create_missing_file(&input_path).map_err(|e| Error::io("Failed to create input file", e))? {
+        println!("Created empty input file \"{}\"", &input_path);
+    } else {
+        println!("Kept existing input file \"{}\"", &input_path);
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
+    let example_result = match &examples.part_1.input {
+        Some(example) => write_missing_example(&example_path, example),
+        None => create_missing_file(&example_path),
+    };
+
+    match example_result.map_err(|e| Error::io("Failed to create example file", e))? {
+        false => {
+            println!("Kept existing example file \"{}\"", &example_path);
+        }
+        true if examples.part_1.input.is_some() => {
+            println!("Created example file \"{}\" from the puzzle", &example_path);
+        }
+        true => {
             println!("Created empty example file \"{}\"", &example_path);
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    }
+
+    if let Some(example) = example_2 {
+        if write_missing_example(&example_2_path, example)
+            .map_err(|e| Error::io("Failed to create example file", e))?
+        {
+            println!(
+                "Created example file \"{}\" from the puzzle",
+                &example_2_path
+            );
+        } else {
+            println!("Kept existing example file \"{}\"", &example_2_path);
         }
     }
 
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
+
+    Ok(())
 }
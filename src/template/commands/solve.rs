@@ -1,8 +1,36 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::error::Error;
+use crate::template::{Day, DaySet, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+/// Runs the solutions of a set of days, one after another.
+pub fn handle(
+    days: &DaySet,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+) -> Result<(), Error> {
+    if submit_part.is_some() && days.len() > 1 {
+        return Err(Error::Usage(
+            "Answers can only be submitted for a single day.".into(),
+        ));
+    }
+
+    for (i, day) in days.iter().enumerate() {
+        if days.len() > 1 {
+            if i > 0 {
+                println!();
+            }
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("------");
+        }
+        solve_day(day, release, dhat, submit_part)?;
+    }
+
+    Ok(())
+}
+
+fn solve_day(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) -> Result<(), Error> {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -23,12 +51,16 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    let status = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status()
+        .map_err(|e| Error::io("Failed to run cargo", e))?;
 
-    cmd.wait().unwrap();
+    if status.success() {
+        Ok(())
+    } else {
+        Err(Error::Solution(format!("Solution of day {day} failed.")))
+    }
 }
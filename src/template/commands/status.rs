@@ -0,0 +1,23 @@
+use std::io;
+
+use tinyjson::JsonValue;
+
+use crate::template::error::Error;
+use crate::template::status;
+
+/// Prints the progress of every day, as a calendar or as JSON.
+pub fn handle(json: bool) -> Result<(), Error> {
+    let statuses = status::collect();
+
+    if json {
+        let value = JsonValue::Array(statuses.iter().map(JsonValue::from).collect());
+        let s = value
+            .format()
+            .map_err(|e| Error::io("Failed to serialize status", io::Error::other(e)))?;
+        println!("{s}");
+    } else {
+        println!("{}", status::format_calendar(&statuses));
+    }
+
+    Ok(())
+}
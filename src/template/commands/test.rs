@@ -0,0 +1,40 @@
+use std::fs;
+use std::process::{Command, Stdio};
+
+use crate::template::error::Error;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::{Day, DaySet, ANSI_BOLD, ANSI_RESET};
+
+/// Runs the tests of the solutions of a set of days. Days without a solution file are skipped.
+pub fn handle(days: &DaySet) -> Result<(), Error> {
+    let mut failed: Vec<Day> = vec![];
+
+    for day in days.iter() {
+        if fs::metadata(get_path_for_bin(day)).is_err() {
+            continue;
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+
+        let status = Command::new("cargo")
+            .args(["test", "--quiet", "--bin", &day.to_string()])
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .map_err(|e| Error::io("Failed to run cargo", e))?;
+
+        if !status.success() {
+            failed.push(day);
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        let days: Vec<String> = failed.iter().map(ToString::to_string).collect();
+        Err(Error::Solution(format!(
+            "Tests of day {} failed.",
+            days.join(", ")
+        )))
+    }
+}
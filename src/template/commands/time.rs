@@ -1,13 +1,26 @@
-use std::collections::HashSet;
-
+use crate::template::allocations::AllocTracking;
+use crate::template::error::Error;
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, history, readme_benchmarks, Day, DaySet};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    days: Option<&DaySet>,
+    run_all: bool,
+    store: bool,
+    alloc_tracking: AllocTracking,
+) -> Result<(), Error> {
+    // a broken file is only replaced when benchmarks are stored, so it does not have to stop other runs.
+    let stored_timings = match Timings::read_from_file() {
+        Ok(timings) => timings,
+        Err(e) if store => return Err(e),
+        Err(e) => {
+            eprintln!("{e}");
+            Timings::default()
+        }
+    };
 
-    let days_to_run = day.map_or_else(
+    let days_to_run = days.map_or_else(
         || {
             if run_all {
                 all_days().collect()
@@ -18,23 +31,50 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |days| days.as_hash_set().clone(),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true, alloc_tracking)?.unwrap_or_default();
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings
+            .store_file()
+            .map_err(|e| Error::io("Failed to store benchmarks", e))?;
+
+        history::append(&timings)
+            .map_err(|e| Error::io("Failed to append benchmarks to history", e))?;
+        readme_benchmarks::update(merged_timings)?;
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
-            }
+        println!("Stored updated benchmarks.");
+    }
+
+    Ok(())
+}
+
+/// Prints the benchmark history of a set of days, or of every day with history if no days are passed.
+pub fn handle_history(days: Option<&DaySet>) {
+    let entries = history::read_from_file();
+
+    let days: Vec<Day> = all_days()
+        .filter(|d| {
+            days.map_or_else(
+                || entries.iter().any(|e| e.day == *d),
+                |days| days.contains(*d),
+            )
+        })
+        .collect();
+
+    if days.is_empty() {
+        println!("No benchmark history. Run `cargo time --store` to record one.");
+        return;
+    }
+
+    for (i, day) in days.into_iter().enumerate() {
+        if i > 0 {
+            println!();
         }
+        history::print_trend(day, &entries);
     }
 }
@@ -0,0 +1,134 @@
+use std::{
+    fs,
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+use chrono::{DateTime, Datelike, Utc};
+
+use crate::template::commands::{download, read, scaffold};
+use crate::template::error::Error;
+use crate::template::runner::InputProblem;
+use crate::template::{aoc_cli, clock, run_multi::get_path_for_bin, Day, LAST_DAY};
+
+/// Delays between attempts to download the input after the puzzle unlocked.
+const RETRY_DELAYS: [u64; 6] = [2, 4, 8, 16, 32, 60];
+
+pub fn handle(wait: bool) -> Result<(), Error> {
+    if wait {
+        return handle_wait();
+    }
+
+    let Some(day) = Day::today() else {
+        return Err(Error::Usage(format!(
+            "`today` command can only be run between day 1 and \
+            day {LAST_DAY} of december. Please use `scaffold` with a specific day."
+        )));
+    };
+
+    download::handle(day)?;
+    scaffold::handle(day, false, None)?;
+    read::handle(day, None)
+}
+
+/// Waits for the next puzzle to unlock, then scaffolds it and downloads its input and puzzle.
+fn handle_wait() -> Result<(), Error> {
+    aoc_cli::check()?;
+
+    let clock = clock::from_env();
+
+    let Some((day, unlock)) = Day::next_unlock(clock.as_ref()) else {
+        return Err(Error::Usage(
+            "Failed to compute the next unlock time.".into(),
+        ));
+    };
+
+    if let Some(year) = std::env::var("AOC_YEAR")
+        .ok()
+        .and_then(|x| x.parse::<i32>().ok())
+    {
+        if year != unlock.year() {
+            eprintln!(
+                "Warning: AOC_YEAR is set to {year}, but the next puzzle unlocks in {}.",
+                unlock.year()
+            );
+        }
+    }
+
+    println!(
+        "Waiting for day {day} to unlock at {} (UTC).",
+        unlock.format("%Y-%m-%d %H:%M:%S")
+    );
+
+    loop {
+        let remaining = (unlock - DateTime::<Utc>::from(clock.now())).num_seconds();
+        if remaining <= 0 {
+            break;
+        }
+        print!("\r⏳ Unlocks in {}   ", format_countdown(remaining));
+        let _ = io::stdout().flush();
+        thread::sleep(Duration::from_secs(1));
+    }
+    println!("\r🔔 Day {day} is unlocked!              ");
+
+    download_with_backoff(day)?;
+
+    if fs::metadata(get_path_for_bin(day)).is_ok() {
+        println!("Module file for day {day} already exists, skipping scaffold.");
+    } else {
+        scaffold::handle(day, false, None)?;
+    }
+
+    read::handle(day, None)
+}
+
+/// Downloads the input, retrying with increasing delays while it is not available yet.
+/// Until then, the website responds with an error message or page, which is not accepted as the input.
+fn download_with_backoff(day: Day) -> Result<(), Error> {
+    let input_path = format!("data/inputs/{day}.txt");
+    let is_input = || {
+        fs::read_to_string(&input_path).is_ok_and(|input| InputProblem::detect(&input).is_none())
+    };
+
+    for (attempt, delay) in RETRY_DELAYS.iter().enumerate() {
+        if aoc_cli::download(day).is_ok() && is_input() {
+            return Ok(());
+        }
+
+        eprintln!(
+            "Input is not available yet, retrying in {delay}s ({}/{}).",
+            attempt + 1,
+            RETRY_DELAYS.len()
+        );
+        thread::sleep(Duration::from_secs(*delay));
+    }
+
+    download::handle(day)?;
+
+    if is_input() {
+        Ok(())
+    } else {
+        Err(Error::Fetch(format!(
+            "The input of day {day} is not available yet, \"{input_path}\" does not contain it. \
+            Run `cargo download {}` again later.",
+            day.into_inner()
+        )))
+    }
+}
+
+fn format_countdown(seconds: i64) -> String {
+    let days = seconds / 86_400;
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds % 86_400 / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    );
+
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
@@ -0,0 +1,215 @@
+//! Head-to-head comparison of alternative implementations of a part.
+//! A day registers alternatives via the `alternatives(...)` option of the `solution!` macro,
+//! `cargo bench-compare <day>` checks that they agree with the main implementation and benches all of them.
+
+use std::env;
+use std::fmt::Display;
+use std::io::{stdout, Write};
+use std::time::{Duration, Instant};
+
+use crate::template::error::Error;
+use crate::template::runner::{bench, read_input};
+use crate::template::table::format_table;
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// The answer and benchmark result of one implementation.
+pub struct Measurement {
+    pub answer: Option<String>,
+    pub duration: Duration,
+    pub samples: u128,
+}
+
+/// A named implementation of a part.
+pub struct Implementation {
+    part: u8,
+    name: &'static str,
+    run: Box<dyn Fn(&str) -> Measurement>,
+}
+
+impl Implementation {
+    pub fn new<T: Display>(
+        part: u8,
+        name: &'static str,
+        func: impl Fn(&str) -> Option<T> + 'static,
+    ) -> Self {
+        Self {
+            part,
+            name,
+            run: Box::new(move |input| {
+                let timer = Instant::now();
+                let answer = func(input);
+                let base_time = timer.elapsed();
+                let (duration, samples) = bench(&func, input, &base_time);
+                Measurement {
+                    answer: answer.map(|x| x.to_string()),
+                    duration,
+                    samples,
+                }
+            }),
+        }
+    }
+}
+
+/// Returns `true` if the solution binary was invoked by `cargo bench-compare`.
+pub fn is_requested() -> bool {
+    env::args().any(|x| x == "--bench-compare")
+}
+
+/// Benches every part that has alternatives against its main implementation and prints a comparison table.
+/// Fails if an alternative disagrees with the main implementation.
+pub fn run(
+    day: Day,
+    main: &[Implementation],
+    alternatives: &[Implementation],
+) -> Result<(), Error> {
+    if alternatives.is_empty() {
+        return Err(Error::Usage(format!(
+            "Day {day} does not register any alternative implementations. \
+            Add e.g. `alternatives(1 => [part_one_naive])` to its `solution!` invocation."
+        )));
+    }
+
+    let input = read_input(day)?;
+    let mut agree = true;
+
+    for main_impl in main {
+        let contenders: Vec<&Implementation> = std::iter::once(main_impl)
+            .chain(alternatives.iter().filter(|x| x.part == main_impl.part))
+            .collect();
+
+        if contenders.len() == 1 {
+            continue;
+        }
+
+        println!("{ANSI_BOLD}Part {}{ANSI_RESET}", main_impl.part);
+
+        let measurements: Vec<Measurement> = contenders
+            .iter()
+            .map(|implementation| {
+                print!("{}", implementation.name);
+                let _ = stdout().flush();
+                let measurement = (implementation.run)(&input);
+                print!("\r{}\r", " ".repeat(implementation.name.len() + 12));
+                measurement
+            })
+            .collect();
+
+        let rows = comparison_rows(&contenders, &measurements);
+        agree &= rows.iter().all(|row| row.agrees);
+        print_table(&rows);
+        println!();
+    }
+
+    if !agree {
+        return Err(Error::Solution(
+            "Alternative implementations disagree with the main implementation.".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+struct Row {
+    name: &'static str,
+    answer: String,
+    time: String,
+    speedup: String,
+    agrees: bool,
+}
+
+fn comparison_rows(contenders: &[&Implementation], measurements: &[Measurement]) -> Vec<Row> {
+    let baseline = &measurements[0];
+
+    contenders
+        .iter()
+        .zip(measurements)
+        .map(|(implementation, measurement)| {
+            let agrees = measurement.answer == baseline.answer;
+            Row {
+                name: implementation.name,
+                answer: match (&measurement.answer, agrees) {
+                    (Some(answer), true) => answer.clone(),
+                    (Some(answer), false) => format!("{answer} ✖"),
+                    (None, true) => "-".into(),
+                    (None, false) => "- ✖".into(),
+                },
+                time: format!(
+                    "{:.1?} @ {} samples",
+                    measurement.duration, measurement.samples
+                ),
+                speedup: format!("{:.2}x", speedup(&baseline.duration, &measurement.duration)),
+                agrees,
+            }
+        })
+        .collect()
+}
+
+/// How many times faster `duration` is compared to `baseline`.
+fn speedup(baseline: &Duration, duration: &Duration) -> f64 {
+    baseline.as_secs_f64() / duration.as_secs_f64().max(f64::EPSILON)
+}
+
+fn print_table(rows: &[Row]) {
+    let rows: Vec<[String; 4]> = rows
+        .iter()
+        .map(|row| {
+            [
+                row.name.to_string(),
+                row.answer.clone(),
+                row.time.clone(),
+                row.speedup.clone(),
+            ]
+        })
+        .collect();
+
+    println!(
+        "{}",
+        format_table(&["Implementation", "Answer", "Time", "Speedup"], &rows)
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{comparison_rows, speedup, Implementation, Measurement};
+
+    fn measurement(answer: Option<&str>, millis: u64) -> Measurement {
+        Measurement {
+            answer: answer.map(String::from),
+            duration: Duration::from_millis(millis),
+            samples: 10,
+        }
+    }
+
+    #[test]
+    fn computes_speedup() {
+        let baseline = Duration::from_millis(10);
+        assert_eq!(speedup(&baseline, &Duration::from_millis(5)), 2.0);
+        assert_eq!(speedup(&baseline, &Duration::from_millis(20)), 0.5);
+    }
+
+    #[test]
+    fn flags_disagreeing_alternatives() {
+        let a = Implementation::new(1, "part_one", |_: &str| Some(1));
+        let b = Implementation::new(1, "part_one_fast", |_: &str| Some(1));
+        let c = Implementation::new(1, "part_one_broken", |_: &str| Some(2));
+
+        let rows = comparison_rows(
+            &[&a, &b, &c],
+            &[
+                measurement(Some("1"), 10),
+                measurement(Some("1"), 5),
+                measurement(Some("2"), 20),
+            ],
+        );
+
+        assert_eq!(rows[1].agrees, true);
+        assert_eq!(rows[1].speedup, "2.00x");
+        assert_eq!(rows[2].agrees, false);
+        assert_eq!(rows[2].answer, "2 ✖");
+        assert_eq!(rows[2].time, "20.0ms @ 10 samples");
+    }
+}
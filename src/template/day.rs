@@ -3,12 +3,60 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
-
+use crate::template::clock::{self, Clock, SERVER_UTC_OFFSET_SECS};
 #[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+use chrono::{DateTime, Datelike, Days, FixedOffset, NaiveDate, Utc};
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// Number of days of the configured event.
+///
+/// Set `AOC_DAYS` in `.cargo/config.toml` for events with another number of days, e.g. other puzzle sites.
+/// Otherwise it follows `AOC_YEAR`, see [`event_days`]. Both are read at compile time,
+/// so the [`day!`](crate::day) macro can validate day numbers.
+pub const LAST_DAY: u8 = last_day(option_env!("AOC_DAYS"), option_env!("AOC_YEAR"));
+
+/// Number of days of an Advent of Code event: 25 up to 2024, 12 from 2025 on.
+pub const fn event_days(year: u16) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+const fn last_day(days: Option<&str>, year: Option<&str>) -> u8 {
+    match (parse_const(days), parse_const(year)) {
+        (Some(days @ 1..=99), _) => days as u8,
+        (Some(_), _) => panic!("AOC_DAYS must be a number between 1 and 99."),
+        (None, Some(year)) => event_days(year),
+        (None, None) => 25,
+    }
+}
+
+/// Parses a decimal number in a const context, returns `None` for missing or malformed values.
+const fn parse_const(value: Option<&str>) -> Option<u16> {
+    let Some(value) = value else {
+        return None;
+    };
+
+    let bytes = value.as_bytes();
+    if bytes.is_empty() || bytes.len() > 4 {
+        return None;
+    }
+
+    let mut result: u16 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            return None;
+        }
+        result = result * 10 + (bytes[i] - b'0') as u16;
+        i += 1;
+    }
+
+    Some(result)
+}
+
+/// A valid day number of the event (i.e. an integer in range 1 to [`LAST_DAY`]).
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -25,7 +73,7 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > LAST_DAY {
             return None;
         }
         Some(Self(day))
@@ -45,16 +93,52 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's between the 1st of december and the last day of the event, `None` otherwise.
+    /// The current time is read from [`clock::from_env`], so it can be overridden with `AOC_NOW`.
     pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        Self::today_at(clock::from_env().as_ref())
+    }
+
+    /// Returns the day at the time of `clock` if it's between the 1st of december and the last day of the event, `None` otherwise.
+    pub fn today_at(clock: &dyn Clock) -> Option<Self> {
+        let today = DateTime::<Utc>::from(clock.now()).with_timezone(&server_offset()?);
+        if today.month() == 12 && today.day() <= u32::from(LAST_DAY) {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
         }
     }
+
+    /// Returns the next day to unlock after the time of `clock`, along with the time it unlocks at.
+    /// Puzzles unlock at midnight in the server timezone (UTC-5).
+    pub fn next_unlock(clock: &dyn Clock) -> Option<(Self, DateTime<Utc>)> {
+        let offset = server_offset()?;
+        let tomorrow = DateTime::<Utc>::from(clock.now())
+            .with_timezone(&offset)
+            .date_naive()
+            .checked_add_days(Days::new(1))?;
+
+        let date = if tomorrow.month() == 12 && tomorrow.day() <= u32::from(LAST_DAY) {
+            tomorrow
+        } else if tomorrow.month() == 12 {
+            NaiveDate::from_ymd_opt(tomorrow.year() + 1, 12, 1)?
+        } else {
+            NaiveDate::from_ymd_opt(tomorrow.year(), 12, 1)?
+        };
+
+        let unlock = date
+            .and_hms_opt(0, 0, 0)?
+            .and_local_timezone(offset)
+            .single()?
+            .with_timezone(&Utc);
+
+        Some((Self::new(u8::try_from(date.day()).ok()?)?, unlock))
+    }
+}
+
+#[cfg(feature = "today")]
+fn server_offset() -> Option<FixedOffset> {
+    FixedOffset::east_opt(i32::try_from(SERVER_UTC_OFFSET_SECS).ok()?)
 }
 
 impl Display for Day {
@@ -94,18 +178,18 @@ impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {LAST_DAY}")
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the event from the 1st to [`LAST_DAY`].
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the event from the 1st to [`LAST_DAY`].
 pub struct AllDays {
     current: u8,
 }
@@ -121,10 +205,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > LAST_DAY {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above `LAST_DAY`.
         let day = Day(self.current);
         self.current += 1;
 
@@ -139,11 +223,11 @@ impl Iterator for AllDays {
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::template::LAST_DAY,
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a value between 1 and the last day of the event (`LAST_DAY`)"
             ),
         );
         $crate::template::Day::__new_unchecked($day)
@@ -154,7 +238,8 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, last_day, Day, LAST_DAY};
+    use crate::template::clock::{parse_timestamp, FixedClock};
 
     #[test]
     fn all_days_iterator() {
@@ -187,6 +272,94 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn last_day_of_event() {
+        assert_eq!(last_day(None, None), 25);
+        assert_eq!(last_day(None, Some("2024")), 25);
+        assert_eq!(last_day(None, Some("2025")), 12);
+        assert_eq!(last_day(Some("20"), Some("2024")), 20);
+        assert_eq!(last_day(Some(""), Some("2015")), 25);
+        assert_eq!(last_day(None, Some("twenty")), 25);
+    }
+
+    #[test]
+    fn days_follow_last_day() {
+        assert_eq!(Day::new(LAST_DAY), Some(Day(LAST_DAY)));
+        assert_eq!(Day::new(LAST_DAY + 1), None);
+        assert_eq!(all_days().count(), usize::from(LAST_DAY));
+        assert_eq!(
+            "0".parse::<Day>().unwrap_err().to_string(),
+            format!("expecting a day number between 1 and {LAST_DAY}")
+        );
+    }
+
+    fn clock_at(timestamp: &str) -> FixedClock {
+        FixedClock(parse_timestamp(timestamp).unwrap())
+    }
+
+    #[test]
+    fn today_at_edges_of_advent() {
+        assert_eq!(Day::today_at(&clock_at("2024-11-30T23:59:59-05:00")), None);
+        assert_eq!(
+            Day::today_at(&clock_at("2024-12-01T00:00:00-05:00")),
+            Some(Day(1))
+        );
+        // already december in UTC, but still november in the server timezone.
+        assert_eq!(Day::today_at(&clock_at("2024-12-01T04:59:59Z")), None);
+        // already the 26th in UTC, but still the 25th in the server timezone.
+        assert_eq!(
+            Day::today_at(&clock_at("2024-12-26T00:30:00Z")),
+            Some(Day(25))
+        );
+        assert_eq!(Day::today_at(&clock_at("2024-12-26T00:00:00-05:00")), None);
+        assert_eq!(Day::today_at(&clock_at("2025-01-01T12:00:00Z")), None);
+    }
+
+    #[test]
+    fn next_unlock_during_advent() {
+        let unlock = |timestamp| {
+            Day::next_unlock(&clock_at(timestamp)).map(|(day, at)| (day, at.to_rfc3339()))
+        };
+
+        assert_eq!(
+            unlock("2024-12-05T12:00:00Z"),
+            Some((Day(6), "2024-12-06T05:00:00+00:00".into()))
+        );
+        // 04:30 UTC is still the previous day in the server timezone.
+        assert_eq!(
+            unlock("2024-12-06T04:30:00Z"),
+            Some((Day(6), "2024-12-06T05:00:00+00:00".into()))
+        );
+        assert_eq!(
+            unlock("2024-12-24T23:59:59-05:00"),
+            Some((Day(25), "2024-12-25T05:00:00+00:00".into()))
+        );
+    }
+
+    #[test]
+    fn next_unlock_outside_advent() {
+        let unlock = |timestamp| {
+            Day::next_unlock(&clock_at(timestamp)).map(|(day, at)| (day, at.to_rfc3339()))
+        };
+
+        assert_eq!(
+            unlock("2024-11-30T23:59:00-05:00"),
+            Some((Day(1), "2024-12-01T05:00:00+00:00".into()))
+        );
+        assert_eq!(
+            unlock("2024-06-15T12:00:00Z"),
+            Some((Day(1), "2024-12-01T05:00:00+00:00".into()))
+        );
+        assert_eq!(
+            unlock("2024-12-26T00:30:00Z"),
+            Some((Day(1), "2025-12-01T05:00:00+00:00".into()))
+        );
+        assert_eq!(
+            unlock("2024-12-31T23:59:59-05:00"),
+            Some((Day(1), "2025-12-01T05:00:00+00:00".into()))
+        );
+    }
 }
 
 /* -------------------------------------------------------------------------- */
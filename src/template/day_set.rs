@@ -0,0 +1,208 @@
+//! Sets of days passed on the command-line, e.g. `cargo time 1-5,7`.
+
+use std::{cell::OnceCell, collections::HashSet, fs, str::FromStr};
+
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::status::{self, Progress};
+use crate::template::{all_days, Day, LAST_DAY};
+
+/// A non-empty set of days. Parsed from a comma-separated list of:
+///  - a day, e.g. `3`.
+///  - a range of days, e.g. `1-5`, `1..5`, `..10` or `20..`. Ranges include both ends.
+///  - `last`: the latest day with a solution file.
+///  - `today`: the current day of the event.
+///  - `unsolved`: the days with a solution file that do not have both stars yet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DaySet(HashSet<Day>);
+
+impl DaySet {
+    /// Every day of the event.
+    pub fn all() -> Self {
+        Self(all_days().collect())
+    }
+
+    /// Parses a set of days. `keywords` is only called if the set contains a keyword.
+    pub fn parse(s: &str, keywords: impl Fn() -> DaySetKeywords) -> Result<Self, String> {
+        let mut days = HashSet::new();
+        let resolved = OnceCell::new();
+        let resolve = || resolved.get_or_init(&keywords);
+
+        for item in s.split(',').map(str::trim) {
+            match item {
+                "last" => days.insert(resolve().last.ok_or("no day has a solution file yet.")?),
+                "today" => days.insert(
+                    resolve()
+                        .today
+                        .ok_or("`today` only exists during the event, with the `today` feature.")?,
+                ),
+                "unsolved" => {
+                    days.extend(&resolve().unsolved);
+                    true
+                }
+                _ => {
+                    let (start, end) = parse_range(item)?;
+                    days.extend(all_days().filter(|day| *day >= start && *day <= end));
+                    true
+                }
+            };
+        }
+
+        if days.is_empty() {
+            return Err(format!("no days match `{s}`."));
+        }
+
+        Ok(Self(days))
+    }
+
+    /// The days in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        all_days().filter(|day| self.0.contains(day))
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.0.contains(&day)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn as_hash_set(&self) -> &HashSet<Day> {
+        &self.0
+    }
+}
+
+impl From<Day> for DaySet {
+    fn from(day: Day) -> Self {
+        Self(HashSet::from([day]))
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, DaySetKeywords::from_project)
+    }
+}
+
+/// Parses a day or a range of days into its first and last day.
+fn parse_range(item: &str) -> Result<(u8, u8), String> {
+    let bound = |s: &str, default: u8| -> Result<u8, String> {
+        if s.is_empty() {
+            return Ok(default);
+        }
+        s.parse::<Day>()
+            .map(Day::into_inner)
+            .map_err(|e| format!("invalid day `{s}`, {e}."))
+    };
+
+    let (start, end) = match item.split_once("..").or_else(|| item.split_once('-')) {
+        Some((start, end)) => (
+            bound(start, 1)?,
+            bound(end.strip_prefix('=').unwrap_or(end), LAST_DAY)?,
+        ),
+        None if item.is_empty() => return Err("empty day in list of days.".into()),
+        None => {
+            let day = bound(item, 0)?;
+            (day, day)
+        }
+    };
+
+    if start > end {
+        return Err(format!("invalid range `{item}`, it ends before it starts."));
+    }
+
+    Ok((start, end))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The days that the keywords of a [`DaySet`] refer to.
+#[derive(Clone, Debug, Default)]
+pub struct DaySetKeywords {
+    pub last: Option<Day>,
+    pub today: Option<Day>,
+    pub unsolved: HashSet<Day>,
+}
+
+impl DaySetKeywords {
+    /// Reads the keywords from the solution files and the answers submitted in the project.
+    pub fn from_project() -> Self {
+        #[cfg(feature = "today")]
+        let today = Day::today();
+        #[cfg(not(feature = "today"))]
+        let today = None;
+
+        Self {
+            last: all_days()
+                .filter(|day| fs::metadata(get_path_for_bin(*day)).is_ok())
+                .last(),
+            today,
+            unsolved: status::collect()
+                .iter()
+                .filter(|s| s.scaffolded && s.progress() != Progress::BothStars)
+                .map(|s| s.day)
+                .collect(),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{DaySet, DaySetKeywords};
+    use crate::day;
+    use crate::template::{Day, LAST_DAY};
+
+    fn parse(s: &str) -> Result<Vec<u8>, String> {
+        let keywords = DaySetKeywords {
+            last: Some(day!(7)),
+            today: None,
+            unsolved: HashSet::from([day!(2), day!(5)]),
+        };
+        DaySet::parse(s, || keywords.clone()).map(|set| set.iter().map(Day::into_inner).collect())
+    }
+
+    #[test]
+    fn parses_days_and_ranges() {
+        assert_eq!(parse("3"), Ok(vec![3]));
+        assert_eq!(parse("1-5"), Ok(vec![1, 2, 3, 4, 5]));
+        assert_eq!(parse("7, 4,1"), Ok(vec![1, 4, 7]));
+        assert_eq!(parse("..3"), Ok(vec![1, 2, 3]));
+        assert_eq!(parse("..=3"), Ok(vec![1, 2, 3]));
+        assert_eq!(parse("2..3,3"), Ok(vec![2, 3]));
+        assert_eq!(
+            parse(&format!("{}..", LAST_DAY - 1)),
+            Ok(vec![LAST_DAY - 1, LAST_DAY])
+        );
+    }
+
+    #[test]
+    fn parses_keywords() {
+        assert_eq!(parse("last"), Ok(vec![7]));
+        assert_eq!(parse("unsolved,1"), Ok(vec![1, 2, 5]));
+        assert!(parse("today").is_err());
+        assert!(DaySet::parse("last", DaySetKeywords::default).is_err());
+        assert!(DaySet::parse("unsolved", DaySetKeywords::default).is_err());
+        // the keywords are only resolved if they are used.
+        assert!(DaySet::parse("1-3", || -> DaySetKeywords { unreachable!() }).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_sets() {
+        assert!(parse("").is_err());
+        assert!(parse("1,,2").is_err());
+        assert!(parse("0").is_err());
+        assert!(parse("5-3").is_err());
+        assert!(parse("1-99").is_err());
+        assert!(parse("first").is_err());
+    }
+}
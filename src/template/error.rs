@@ -0,0 +1,120 @@
+//! Errors of the template commands.
+//! Commands return them instead of exiting, so they can be called from other tools.
+//! Only the binaries turn an error into a message and an exit code, see [`Error::exit_code`].
+use std::{fmt::Display, io};
+
+use crate::template::aoc_cli::AocCommandError;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Invalid command-line arguments or configuration, e.g. an unknown template or a missing env var.
+    Usage(String),
+    /// A file the command depends on does not exist yet, e.g. a puzzle that was not downloaded.
+    NotFound(String),
+    /// aoc-cli is not installed or failed.
+    AocCli(AocCommandError),
+    /// Fetching data from the website failed.
+    Fetch(String),
+    /// Reading or writing a file, or starting a process, failed.
+    Io { context: String, source: io::Error },
+    /// A solution failed to run or gave different answers than expected.
+    Solution(String),
+    /// Files that must not be published, e.g. puzzle inputs, are about to be committed.
+    Data(String),
+}
+
+impl Error {
+    pub fn io(context: impl Into<String>, source: io::Error) -> Self {
+        Self::Io {
+            context: context.into(),
+            source,
+        }
+    }
+
+    /// Exit code of a binary that fails with this error. `1` is left to panics.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => 2,
+            Error::NotFound(_) => 3,
+            Error::AocCli(_) => 4,
+            Error::Fetch(_) => 5,
+            Error::Io { .. } => 6,
+            Error::Solution(_) => 7,
+            Error::Data(_) => 8,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(message)
+            | Error::NotFound(message)
+            | Error::Fetch(message)
+            | Error::Solution(message)
+            | Error::Data(message) => write!(f, "{message}"),
+            Error::AocCli(AocCommandError::CommandNotFound) => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            Error::AocCli(e) => write!(f, "failed to call aoc-cli: {e}"),
+            Error::Io { context, source } => write!(f, "{context}: {source}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<AocCommandError> for Error {
+    fn from(e: AocCommandError) -> Self {
+        Error::AocCli(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io;
+
+    use super::Error;
+    use crate::template::aoc_cli::AocCommandError;
+
+    #[test]
+    fn exit_codes_are_distinct() {
+        let errors = [
+            Error::Usage(String::new()),
+            Error::NotFound(String::new()),
+            Error::AocCli(AocCommandError::CommandNotFound),
+            Error::Fetch(String::new()),
+            Error::io("", io::Error::from(io::ErrorKind::NotFound)),
+            Error::Solution(String::new()),
+            Error::Data(String::new()),
+        ];
+
+        let mut codes: Vec<i32> = errors.iter().map(Error::exit_code).collect();
+        assert_eq!(codes.contains(&0), false);
+        assert_eq!(codes.contains(&1), false);
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len());
+    }
+
+    #[test]
+    fn formats_errors() {
+        assert_eq!(
+            Error::io("Failed to write report file", io::Error::other("disk full")).to_string(),
+            "Failed to write report file: disk full"
+        );
+        assert_eq!(
+            Error::from(AocCommandError::CommandNotCallable).to_string(),
+            "failed to call aoc-cli: aoc-cli could not be called."
+        );
+        assert_eq!(
+            Error::from(AocCommandError::CommandNotFound)
+                .to_string()
+                .contains("cargo install aoc-cli"),
+            true
+        );
+    }
+}
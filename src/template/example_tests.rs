@@ -0,0 +1,237 @@
+//! Results of the example tests of the solutions, see `cargo check-examples`.
+//! Each solution binary is tested on its own and the libtest output is mapped to the parts of the day.
+
+use std::{
+    collections::HashMap,
+    process::{Command, Output},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Outcome of the tests of one part. Ordered by precedence when a part has multiple tests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Outcome {
+    /// The solution has no test for the part.
+    Missing,
+    Ignored,
+    Passed,
+    Failed,
+}
+
+impl Outcome {
+    fn key(self) -> &'static str {
+        match self {
+            Outcome::Missing => "missing",
+            Outcome::Ignored => "ignored",
+            Outcome::Passed => "passed",
+            Outcome::Failed => "failed",
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Outcome::Missing => "-",
+            Outcome::Ignored => "○",
+            Outcome::Passed => "✓",
+            Outcome::Failed => "✖",
+        }
+    }
+
+    /// Combines the outcomes of two tests of the same part. A failure wins over a pass, a pass over an ignored test.
+    fn and(self, other: Outcome) -> Outcome {
+        self.max(other)
+    }
+}
+
+/// Example test results of one day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayResult {
+    pub day: Day,
+    /// `false` if the solution did not compile.
+    pub built: bool,
+    pub part_1: Outcome,
+    pub part_2: Outcome,
+}
+
+impl DayResult {
+    pub fn passed(&self) -> bool {
+        self.built && self.part_1 != Outcome::Failed && self.part_2 != Outcome::Failed
+    }
+}
+
+/// Runs the tests of the solution of a day, without printing their output.
+pub fn run(day: Day) -> std::io::Result<DayResult> {
+    let output = Command::new("cargo")
+        .args(["test", "--bin", &day.to_string()])
+        .output()?;
+
+    Ok(parse_output(day, &output))
+}
+
+fn parse_output(day: Day, output: &Output) -> DayResult {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let [part_1, part_2] = parse_test_lines(&stdout);
+
+    DayResult {
+        day,
+        // libtest prints its summary even if tests fail, a failed build never gets that far.
+        built: output.status.success() || stdout.contains("test result:"),
+        part_1,
+        part_2,
+    }
+}
+
+/// Maps the result lines of libtest, e.g. `test tests::test_part_one ... ok`, to the outcome of both parts.
+/// Tests are assigned to a part by their name containing `part_one` or `part_two`.
+pub fn parse_test_lines(stdout: &str) -> [Outcome; 2] {
+    let mut outcomes = [Outcome::Missing; 2];
+
+    for line in stdout.lines() {
+        let Some((name, result)) = line
+            .strip_prefix("test ")
+            .and_then(|line| line.split_once(" ... "))
+        else {
+            continue;
+        };
+
+        let outcome = match result.trim() {
+            "ok" => Outcome::Passed,
+            "FAILED" => Outcome::Failed,
+            result if result.starts_with("ignored") => Outcome::Ignored,
+            _ => continue,
+        };
+
+        let part = if name.contains("part_one") {
+            0
+        } else if name.contains("part_two") {
+            1
+        } else {
+            continue;
+        };
+
+        outcomes[part] = outcomes[part].and(outcome);
+    }
+
+    outcomes
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders the results as a grid of days and parts, followed by the number of passing parts.
+pub fn format_grid(results: &[DayResult]) -> String {
+    let mut lines = vec![format!("{ANSI_ITALIC}Day  Part 1  Part 2{ANSI_RESET}")];
+
+    for result in results {
+        if result.built {
+            lines.push(format!(
+                "{}   {:<6}  {}",
+                result.day,
+                result.part_1.symbol(),
+                result.part_2.symbol()
+            ));
+        } else {
+            lines.push(format!("{}   ✖ build failed", result.day));
+        }
+    }
+
+    let parts: Vec<Outcome> = results
+        .iter()
+        .filter(|r| r.built)
+        .flat_map(|r| [r.part_1, r.part_2])
+        .collect();
+    let tested = parts
+        .iter()
+        .filter(|x| matches!(x, Outcome::Passed | Outcome::Failed))
+        .count();
+    let passed = parts.iter().filter(|x| **x == Outcome::Passed).count();
+
+    lines.push(String::new());
+    lines.push(format!(
+        "{ANSI_BOLD}{passed} / {tested} parts pass{ANSI_RESET}"
+    ));
+    lines.push("✓ passed, ✖ failed, ○ ignored, - no test".into());
+
+    lines.join("\n")
+}
+
+impl From<&DayResult> for JsonValue {
+    fn from(value: &DayResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("built".into(), JsonValue::Boolean(value.built));
+        map.insert(
+            "part_1".into(),
+            JsonValue::String(value.part_1.key().into()),
+        );
+        map.insert(
+            "part_2".into(),
+            JsonValue::String(value.part_2.key().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_grid, parse_test_lines, DayResult, Outcome};
+    use crate::day;
+
+    const OUTPUT: &str = "
+running 3 tests
+test tests::test_part_one ... ok
+test tests::test_part_two ... FAILED
+test tests::test_part_two_second_example ... ok
+test tests::test_parse ... ok
+
+failures:
+
+---- tests::test_part_two stdout ----
+assertion failed
+
+test result: FAILED. 3 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out
+";
+
+    #[test]
+    fn parses_test_output() {
+        assert_eq!(parse_test_lines(OUTPUT), [Outcome::Passed, Outcome::Failed]);
+        assert_eq!(
+            parse_test_lines("test tests::test_part_one ... ignored, slow\n"),
+            [Outcome::Ignored, Outcome::Missing]
+        );
+        assert_eq!(parse_test_lines(""), [Outcome::Missing; 2]);
+    }
+
+    #[test]
+    fn formats_grid() {
+        let results = [
+            DayResult {
+                day: day!(1),
+                built: true,
+                part_1: Outcome::Passed,
+                part_2: Outcome::Failed,
+            },
+            DayResult {
+                day: day!(2),
+                built: false,
+                part_1: Outcome::Missing,
+                part_2: Outcome::Missing,
+            },
+        ];
+
+        let grid = format_grid(&results);
+        assert_eq!(grid.contains("01   ✓       ✖"), true);
+        assert_eq!(grid.contains("02   ✖ build failed"), true);
+        assert_eq!(grid.contains("1 / 2 parts pass"), true);
+        assert_eq!(results[0].passed(), false);
+
+        let json = JsonValue::from(&results[0]).stringify().unwrap();
+        assert_eq!(json.contains("\"part_2\":\"failed\""), true);
+    }
+}
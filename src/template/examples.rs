@@ -0,0 +1,309 @@
+//! Extraction of example inputs and expected answers from a downloaded puzzle description.
+//! The example input is the first code block of a part that is introduced as an example,
+//! the expected answer is the last emphasized code span of the part (e.g. `` `*11*` ``).
+
+use crate::template::puzzle;
+
+/// Example of one part of a puzzle.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartExample {
+    pub input: Option<String>,
+    pub answer: Option<String>,
+}
+
+/// Examples of both parts of a puzzle. Part two is only present once part one was solved.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzleExamples {
+    pub part_1: PartExample,
+    pub part_2: PartExample,
+}
+
+impl PuzzleExamples {
+    /// Input of the example file for part two, if it differs from the one of part one.
+    pub fn separate_part_2_input(&self) -> Option<&str> {
+        self.part_2
+            .input
+            .as_deref()
+            .filter(|input| Some(*input) != self.part_1.input.as_deref())
+    }
+}
+
+/// Extracts the examples of both parts from a puzzle description.
+pub fn extract(puzzle: &str) -> PuzzleExamples {
+    let puzzle = normalize_html(puzzle);
+
+    let (part_1, part_2) = puzzle::split_parts(&puzzle);
+
+    PuzzleExamples {
+        part_1: extract_part(part_1, true),
+        // part two often reuses the example of part one, its first code block is not necessarily an input.
+        part_2: extract_part(part_2.unwrap_or_default(), false),
+    }
+}
+
+/// Formats an answer as a literal for a test assertion. Solutions return unsigned integers, e.g. `Option<u32>`,
+/// so other answers, e.g. `-12` or `abc`, cannot be asserted and return `None`.
+pub fn answer_literal(answer: &str) -> Option<String> {
+    answer.parse::<u64>().ok().map(|answer| answer.to_string())
+}
+
+/// Comment for an answer that cannot be asserted, see [`answer_literal`].
+pub fn answer_comment(answer: &str) -> String {
+    format!("// expected: {answer:?}")
+}
+
+/// Replaces `assert_eq!(result, None);` in the tests of a solution with the expected answers.
+/// Answers that cannot be asserted are added as a comment instead, see [`answer_literal`].
+/// Assertions that were already changed are kept. Returns the new source and the parts that were filled in.
+pub fn fill_assertions(source: &str, answers: [Option<&str>; 2]) -> (String, Vec<u8>) {
+    const PLACEHOLDER: &str = "assert_eq!(result, None);";
+
+    let mut source = source.to_string();
+    let mut filled = vec![];
+
+    for (part, test_name) in [(1, "fn test_part_one"), (2, "fn test_part_two")] {
+        let Some(answer) = answers[usize::from(part - 1)] else {
+            continue;
+        };
+        let Some(test_start) = source.find(test_name) else {
+            continue;
+        };
+        let test_end = source[test_start + test_name.len()..]
+            .find("fn ")
+            .map_or(source.len(), |i| test_start + test_name.len() + i);
+
+        let Some(i) = source[test_start..test_end].find(PLACEHOLDER) else {
+            continue;
+        };
+        let start = test_start + i;
+        let end = start + PLACEHOLDER.len();
+
+        if let Some(literal) = answer_literal(answer) {
+            source.replace_range(start..end, &format!("assert_eq!(result, Some({literal}));"));
+            filled.push(part);
+        } else if !source[end..].starts_with(" // expected:") {
+            source.insert_str(end, &format!(" {}", answer_comment(answer)));
+        }
+    }
+
+    (source, filled)
+}
+
+/// Makes the test of part two read the example file of part two, e.g. `01-2.txt`.
+pub fn use_part_two_example(source: &str) -> String {
+    let Some(test_start) = source.find("fn test_part_two") else {
+        return source.to_string();
+    };
+
+    let (head, tail) = source.split_at(test_start);
+    format!(
+        "{head}{}",
+        tail.replacen(
+            "read_file(\"examples\", DAY)",
+            "read_file_part(\"examples\", DAY, 2)",
+            1
+        )
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A part of a puzzle description, split into prose and code blocks.
+enum Block<'a> {
+    Text(String),
+    Code(&'a str),
+}
+
+fn extract_part(text: &str, fallback_to_first_block: bool) -> PartExample {
+    let blocks = split_blocks(text);
+
+    let mut input = None;
+    let mut fallback = None;
+    let mut preceding = String::new();
+    let mut answer = None;
+
+    for block in &blocks {
+        match block {
+            Block::Code(code) => {
+                if input.is_none() && preceding.to_lowercase().contains("example") {
+                    input = Some(code.to_string());
+                }
+                if fallback_to_first_block {
+                    fallback.get_or_insert(code.to_string());
+                }
+                preceding.clear();
+            }
+            Block::Text(text) => {
+                for line in text.lines() {
+                    // the website adds the submitted answer below solved parts.
+                    if line.contains("Your puzzle answer was") {
+                        continue;
+                    }
+                    if let Some(emphasized) = emphasized_code(line).pop() {
+                        answer = Some(emphasized);
+                    }
+                }
+                preceding.push_str(text);
+            }
+        }
+    }
+
+    PartExample {
+        input: input.or(fallback).filter(|x| !x.trim().is_empty()),
+        answer,
+    }
+}
+
+fn split_blocks(text: &str) -> Vec<Block<'_>> {
+    let mut blocks = vec![];
+    let mut prose = String::new();
+    let mut code_start: Option<usize> = None;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let is_fence = line.trim_start().starts_with("```");
+
+        match (code_start, is_fence) {
+            (None, true) => {
+                blocks.push(Block::Text(std::mem::take(&mut prose)));
+                code_start = Some(offset + line.len());
+            }
+            (Some(start), true) => {
+                blocks.push(Block::Code(&text[start..offset]));
+                code_start = None;
+            }
+            (None, false) => prose.push_str(line),
+            (Some(_), false) => {}
+        }
+
+        offset += line.len();
+    }
+
+    blocks.push(Block::Text(prose));
+    blocks
+}
+
+/// Contents of the emphasized code spans of a line, e.g. `` `*11*` `` or `` *`11`* ``.
+fn emphasized_code(line: &str) -> Vec<String> {
+    let parts: Vec<&str> = line.split('`').collect();
+
+    (1..parts.len())
+        .step_by(2)
+        // an odd number of backticks leaves the last span unclosed.
+        .filter(|i| i + 1 < parts.len())
+        .filter_map(|i| {
+            let code = parts[i];
+            let inner = code.trim_matches('*');
+
+            let emphasized_inside = inner.len() < code.len();
+            let emphasized_outside = parts[i - 1].ends_with('*') && parts[i + 1].starts_with('*');
+
+            (!inner.is_empty() && (emphasized_inside || emphasized_outside))
+                .then(|| inner.to_string())
+        })
+        .collect()
+}
+
+/// Converts the HTML markup of a description that was not converted to markdown.
+fn normalize_html(puzzle: &str) -> String {
+    puzzle
+        .replace("<pre><code>", "\n```\n")
+        .replace("\n</code></pre>", "\n```\n")
+        .replace("</code></pre>", "\n```\n")
+        .replace("<code><em>", "`*")
+        .replace("</em></code>", "*`")
+        .replace("<em><code>", "*`")
+        .replace("</code></em>", "`*")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        answer_comment, answer_literal, emphasized_code, extract, fill_assertions,
+        use_part_two_example,
+    };
+
+    const PUZZLE: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/src/template/fixtures/puzzle.md"
+    ));
+
+    #[test]
+    fn extracts_examples() {
+        let examples = extract(PUZZLE);
+
+        assert_eq!(
+            examples.part_1.input.as_deref(),
+            Some("3 1 2\n5 4 6\n9 8 7\n")
+        );
+        assert_eq!(examples.part_1.answer.as_deref(), Some("6"));
+        assert_eq!(examples.part_2.input.as_deref(), Some("3 1 2 0\n5 4 6 0\n"));
+        assert_eq!(examples.part_2.answer.as_deref(), Some("7"));
+        assert_eq!(examples.separate_part_2_input(), Some("3 1 2 0\n5 4 6 0\n"));
+    }
+
+    #[test]
+    fn extracts_examples_before_part_two_unlocks() {
+        let part_1 = PUZZLE.split("## --- Part Two ---").next().unwrap();
+        let examples = extract(part_1);
+
+        assert_eq!(examples.part_1.answer.as_deref(), Some("6"));
+        assert_eq!(examples.part_2.input, None);
+        assert_eq!(examples.part_2.answer, None);
+    }
+
+    #[test]
+    fn extracts_examples_from_html() {
+        let examples = extract(
+            "<p>For example:</p>\n<pre><code>1\n2\n</code></pre>\n<p>This gives <code><em>3</em></code>.</p>",
+        );
+
+        assert_eq!(examples.part_1.input.as_deref(), Some("1\n2\n"));
+        assert_eq!(examples.part_1.answer.as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn finds_emphasized_code() {
+        assert_eq!(
+            emphasized_code("the sum of `1 + 2` is `*3*`, not *`4`* or `5`"),
+            vec!["3", "4"]
+        );
+        assert_eq!(emphasized_code("unclosed `*3*"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn fills_assertions() {
+        let source = "fn test_part_one() {\n    assert_eq!(result, None);\n}\n\nfn test_part_two() {\n    assert_eq!(result, Some(1));\n}\n";
+
+        let (filled, parts) = fill_assertions(source, [Some("6"), Some("7")]);
+        assert_eq!(parts, vec![1]);
+        assert_eq!(filled.contains("assert_eq!(result, Some(6));"), true);
+        assert_eq!(filled.contains("assert_eq!(result, Some(1));"), true);
+
+        let source = "fn test_part_one() {\n    read_file(\"examples\", DAY)\n}\nfn test_part_two() {\n    read_file(\"examples\", DAY)\n}\n";
+        assert_eq!(
+            use_part_two_example(source),
+            "fn test_part_one() {\n    read_file(\"examples\", DAY)\n}\nfn test_part_two() {\n    read_file_part(\"examples\", DAY, 2)\n}\n"
+        );
+
+        let source = "fn test_part_one() {\n    assert_eq!(result, None);\n}\n";
+        let (filled, parts) = fill_assertions(source, [Some("-12"), None]);
+        assert_eq!(parts, Vec::<u8>::new());
+        assert_eq!(
+            filled,
+            "fn test_part_one() {\n    assert_eq!(result, None); // expected: \"-12\"\n}\n"
+        );
+        // the comment is only added once.
+        assert_eq!(fill_assertions(&filled, [Some("-12"), None]).0, filled);
+
+        assert_eq!(answer_literal("4512"), Some("4512".into()));
+        assert_eq!(answer_literal("-12"), None);
+        assert_eq!(answer_literal("abc"), None);
+        assert_eq!(answer_comment("abc"), "// expected: \"abc\"");
+    }
+}
@@ -0,0 +1,422 @@
+//! Property-based cross-checking of a solution part against a reference implementation.
+//! A day registers an input generator and a (slow but obviously correct) reference implementation
+//! via the `fuzz(...)` option of the `solution!` macro. `cargo fuzz-check <day>` then feeds random
+//! inputs to both and reports the first disagreement, shrunk to a minimal case.
+
+use std::cell::Cell;
+use std::env;
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::template::error::Error;
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const DEFAULT_ITERATIONS: u64 = 10_000;
+const MAX_SHRINK_ATTEMPTS: usize = 10_000;
+
+/// A small, seedable pseudo-random number generator (`SplitMix64`) for input generators.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in the given inclusive range.
+    pub fn range(&mut self, range: std::ops::RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "empty range passed to `Rng::range`");
+        match (end - start).checked_add(1) {
+            Some(span) => start + self.next_u64() % span,
+            None => self.next_u64(),
+        }
+    }
+
+    /// Returns `true` with a probability of `1 / n`.
+    pub fn one_in(&mut self, n: u64) -> bool {
+        self.range(1..=n.max(1)) == 1
+    }
+
+    /// Picks a random element of a non-empty slice.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        #[allow(clippy::cast_possible_truncation)]
+        let index = self.range(0..=(items.len() as u64 - 1)) as usize;
+        &items[index]
+    }
+}
+
+/// The observable result of running a part against an input. Panics compare equal to each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Answer(Option<String>),
+    Panic,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Answer(Some(answer)) => write!(f, "{answer}"),
+            Outcome::Answer(None) => write!(f, "✖"),
+            Outcome::Panic => write!(f, "panicked"),
+        }
+    }
+}
+
+type Solver = Box<dyn Fn(&str) -> Outcome>;
+type Generator = Box<dyn Fn(&mut Rng) -> String>;
+
+/// A fast implementation of a part paired with a reference implementation and an input generator.
+pub struct FuzzTarget {
+    part: u8,
+    name: &'static str,
+    reference_name: &'static str,
+    solver: Solver,
+    reference: Solver,
+    generator: Generator,
+}
+
+impl FuzzTarget {
+    pub fn new<T: Display, R: Display>(
+        part: u8,
+        (name, solver): (&'static str, impl Fn(&str) -> Option<T> + 'static),
+        (reference_name, reference): (&'static str, impl Fn(&str) -> Option<R> + 'static),
+        generator: impl Fn(&mut Rng) -> String + 'static,
+    ) -> Self {
+        Self {
+            part,
+            name,
+            reference_name,
+            solver: Box::new(move |input| observe(&solver, input)),
+            reference: Box::new(move |input| observe(&reference, input)),
+            generator: Box::new(generator),
+        }
+    }
+
+    fn outcomes(&self, input: &str) -> (Outcome, Outcome) {
+        ((self.solver)(input), (self.reference)(input))
+    }
+
+    fn disagrees(&self, input: &str) -> bool {
+        let (a, b) = self.outcomes(input);
+        a != b
+    }
+}
+
+fn observe<T: Display>(func: &impl Fn(&str) -> Option<T>, input: &str) -> Outcome {
+    panic::catch_unwind(AssertUnwindSafe(|| func(input).map(|x| x.to_string())))
+        .map_or(Outcome::Panic, Outcome::Answer)
+}
+
+/// A counterexample on which the two implementations of a part disagree.
+pub struct Disagreement {
+    pub iteration: u64,
+    pub input: String,
+    pub outcome: Outcome,
+    pub reference_outcome: Outcome,
+}
+
+/// Feeds `iterations` generated inputs to both implementations of `target`.
+/// Returns the first disagreement, shrunk to a minimal input, or `None` if all inputs agree.
+pub fn check(target: &FuzzTarget, iterations: u64, rng: &mut Rng) -> Option<Disagreement> {
+    (1..=iterations).find_map(|iteration| {
+        let input = (target.generator)(rng);
+        if !target.disagrees(&input) {
+            return None;
+        }
+        let input = shrink(&input, |candidate| target.disagrees(candidate));
+        let (outcome, reference_outcome) = target.outcomes(&input);
+        Some(Disagreement {
+            iteration,
+            input,
+            outcome,
+            reference_outcome,
+        })
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Greedily shrinks a failing text input while `is_failing` holds.
+/// Tries removing chunks of lines, then single tokens of a line, then moves numbers towards zero.
+pub fn shrink(input: &str, is_failing: impl Fn(&str) -> bool) -> String {
+    let mut current = input.to_string();
+    let attempts = Cell::new(0);
+
+    let accept = |candidate: String, current: &mut String| {
+        attempts.set(attempts.get() + 1);
+        if candidate != *current && is_failing(&candidate) {
+            *current = candidate;
+            true
+        } else {
+            false
+        }
+    };
+
+    loop {
+        let before = current.clone();
+
+        // 1. remove chunks of lines, starting with large chunks.
+        let mut chunk = current.lines().count() / 2;
+        while chunk > 0 {
+            let mut start = 0;
+            while start < current.lines().count() {
+                let lines: Vec<&str> = current.lines().collect();
+                let candidate = join_lines(
+                    lines
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| *i < start || *i >= start + chunk)
+                        .map(|(_, l)| *l),
+                    &current,
+                );
+                if !accept(candidate, &mut current) {
+                    start += chunk;
+                }
+            }
+            chunk /= 2;
+        }
+
+        // 2. remove single tokens of each line.
+        let mut line_index = 0;
+        while line_index < current.lines().count() {
+            let mut token_index = 0;
+            loop {
+                let lines: Vec<&str> = current.lines().collect();
+                let tokens: Vec<&str> = lines[line_index].split(' ').collect();
+                if token_index >= tokens.len() || tokens.len() == 1 {
+                    break;
+                }
+                let line = tokens
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != token_index)
+                    .map(|(_, t)| *t)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let candidate = replace_line(&current, line_index, &line);
+                if !accept(candidate, &mut current) {
+                    token_index += 1;
+                }
+            }
+            line_index += 1;
+        }
+
+        // 3. move numeric tokens towards zero.
+        for line_index in 0..current.lines().count() {
+            let token_count = current.lines().nth(line_index).unwrap().split(' ').count();
+            for token_index in 0..token_count {
+                let line = current.lines().nth(line_index).unwrap().to_string();
+                let tokens: Vec<&str> = line.split(' ').collect();
+                let Ok(value) = tokens[token_index].parse::<i64>() else {
+                    continue;
+                };
+                for smaller in [0, value / 2, value - value.signum()] {
+                    if smaller == value {
+                        continue;
+                    }
+                    let mut tokens = tokens.clone();
+                    let smaller = smaller.to_string();
+                    tokens[token_index] = &smaller;
+                    let candidate = replace_line(&current, line_index, &tokens.join(" "));
+                    if accept(candidate, &mut current) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if current == before || attempts.get() > MAX_SHRINK_ATTEMPTS {
+            return current;
+        }
+    }
+}
+
+fn join_lines<'a>(lines: impl Iterator<Item = &'a str>, original: &str) -> String {
+    let mut s = lines.collect::<Vec<_>>().join("\n");
+    if original.ends_with('\n') && !s.is_empty() {
+        s.push('\n');
+    }
+    s
+}
+
+fn replace_line(input: &str, index: usize, replacement: &str) -> String {
+    join_lines(
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, l)| if i == index { replacement } else { l }),
+        input,
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns `true` if the solution binary was invoked by `cargo fuzz-check`.
+pub fn is_requested() -> bool {
+    env::args().any(|x| x == "--fuzz-check")
+}
+
+/// Runs all registered fuzz targets with the iterations and seed passed on the command-line
+/// and fails if a disagreement was found.
+pub fn run(day: Day, targets: &[FuzzTarget]) -> Result<(), Error> {
+    if targets.is_empty() {
+        return Err(Error::Usage(format!(
+            "Day {day} does not register any fuzz targets. \
+            Add e.g. `fuzz(generate_input, 1 => part_one_naive)` to its `solution!` invocation."
+        )));
+    }
+
+    let iterations = arg_value("--iterations").unwrap_or(DEFAULT_ITERATIONS);
+    let seed = arg_value("--seed").unwrap_or_else(random_seed);
+
+    // suppress panic messages of failing candidates, they are reported as outcomes instead.
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut failed = false;
+
+    for target in targets {
+        let part = format!("Part {}", target.part);
+        println!(
+            "{part}: {ANSI_ITALIC}checking `{}` against `{}` with {iterations} inputs (seed {seed}){ANSI_RESET}",
+            target.name, target.reference_name
+        );
+
+        let mut rng = Rng::new(seed);
+        match check(target, iterations, &mut rng) {
+            None => println!("{part}: ✔ all inputs agree"),
+            Some(disagreement) => {
+                failed = true;
+                println!(
+                    "{part}: ✖ disagreement on input #{}, shrunk to:",
+                    disagreement.iteration
+                );
+                println!("---");
+                println!("{}", disagreement.input.trim_end_matches('\n'));
+                println!("---");
+                println!(
+                    "  {}: {ANSI_BOLD}{}{ANSI_RESET}",
+                    target.name, disagreement.outcome
+                );
+                println!(
+                    "  {}: {ANSI_BOLD}{}{ANSI_RESET}",
+                    target.reference_name, disagreement.reference_outcome
+                );
+            }
+        }
+    }
+
+    panic::set_hook(default_hook);
+
+    if failed {
+        return Err(Error::Solution(
+            "Solutions disagree with their reference implementations.".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn arg_value(name: &str) -> Option<u64> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == name)?;
+    args.get(index + 1)?.parse().ok()
+}
+
+fn random_seed() -> u64 {
+    #[allow(clippy::cast_possible_truncation)]
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, shrink, FuzzTarget, Outcome, Rng};
+
+    #[test]
+    fn rng_is_deterministic_and_in_range() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..1000 {
+            let x = a.range(3..=7);
+            assert_eq!(x, b.range(3..=7));
+            assert!((3..=7).contains(&x));
+        }
+    }
+
+    #[test]
+    fn shrinks_lines_tokens_and_numbers() {
+        let input = "1 2 3\n4 50 6\n7 8 9\n";
+        let shrunk = shrink(input, |s| {
+            s.split_whitespace().any(|t| t == "25" || t == "50")
+        });
+        assert_eq!(shrunk, "25\n");
+    }
+
+    #[test]
+    fn finds_and_shrinks_disagreements() {
+        let sum = |s: &str| {
+            Some(
+                s.split_whitespace()
+                    .map(|x| x.parse::<u32>().unwrap())
+                    .sum::<u32>(),
+            )
+        };
+        let buggy_sum = |s: &str| {
+            Some(
+                s.split_whitespace()
+                    .map(|x| x.parse::<u32>().unwrap())
+                    .filter(|x| *x != 13)
+                    .sum::<u32>(),
+            )
+        };
+        let target = FuzzTarget::new(
+            1,
+            ("buggy_sum", buggy_sum),
+            ("sum", sum),
+            |rng: &mut Rng| {
+                (0..5)
+                    .map(|_| rng.range(0..=20).to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            },
+        );
+
+        let disagreement = check(&target, 1000, &mut Rng::new(1)).unwrap();
+        assert_eq!(disagreement.input, "13");
+        assert_eq!(disagreement.outcome, Outcome::Answer(Some("0".into())));
+        assert_eq!(
+            disagreement.reference_outcome,
+            Outcome::Answer(Some("13".into()))
+        );
+    }
+
+    #[test]
+    fn treats_panics_as_outcomes() {
+        let target = FuzzTarget::new(
+            1,
+            ("a", |s: &str| Some(s.parse::<u32>().unwrap())),
+            ("b", |s: &str| Some(s.parse::<u32>().expect("not a number"))),
+            |rng: &mut Rng| {
+                if rng.one_in(2) {
+                    "x".into()
+                } else {
+                    rng.range(0..=9).to_string()
+                }
+            },
+        );
+        assert!(check(&target, 100, &mut Rng::new(7)).is_none());
+    }
+}
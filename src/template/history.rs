@@ -0,0 +1,489 @@
+//! Append-only history of stored benchmarks.
+//! Every `cargo time --store` appends one JSON line per benched day to the history file,
+//! tagged with a timestamp, the current git commit and the machine the benchmark ran on.
+
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    allocations::AllocStats,
+    clock::{self, civil_from_days, Clock},
+    table::format_table,
+    timings::{format_nanos, Timing, Timings},
+    Day, ANSI_BOLD, ANSI_RESET,
+};
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.jsonl";
+
+/// Benchmark statistics of a single part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub nanos: f64,
+    pub alloc: Option<AllocStats>,
+    pub samples: Option<u64>,
+}
+
+/// One benchmark of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    /// Unix timestamp in seconds.
+    pub timestamp: u64,
+    /// Abbreviated commit hash, suffixed with `-dirty` if the working tree had changes.
+    pub commit: Option<String>,
+    pub machine: String,
+    pub day: Day,
+    pub part_1: Option<PartRecord>,
+    pub part_2: Option<PartRecord>,
+    pub total_nanos: f64,
+}
+
+impl HistoryEntry {
+    pub fn from_timing(
+        timing: &Timing,
+        timestamp: u64,
+        commit: Option<String>,
+        machine: &str,
+    ) -> Self {
+        let record = |time: Option<f64>, alloc: Option<AllocStats>, samples: Option<u64>| {
+            time.map(|nanos| PartRecord {
+                nanos,
+                alloc,
+                samples,
+            })
+        };
+
+        Self {
+            timestamp,
+            commit,
+            machine: machine.to_string(),
+            day: timing.day,
+            part_1: record(timing.part_1, timing.part_1_alloc, timing.part_1_samples),
+            part_2: record(timing.part_2, timing.part_2_alloc, timing.part_2_samples),
+            total_nanos: timing.total_nanos,
+        }
+    }
+}
+
+/// Appends an entry for every timing to the history file.
+/// The timestamp is read from [`clock::from_env`], so it can be overridden with `AOC_NOW`.
+pub fn append(timings: &Timings) -> Result<(), io::Error> {
+    let entries = entries_at(
+        timings,
+        clock::from_env().as_ref(),
+        current_commit(),
+        &machine_id(),
+    );
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE_PATH)?;
+
+    for entry in entries {
+        let line = JsonValue::from(&entry)
+            .stringify()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+        writeln!(file, "{line}")?;
+    }
+
+    Ok(())
+}
+
+fn entries_at(
+    timings: &Timings,
+    clock: &dyn Clock,
+    commit: Option<String>,
+    machine: &str,
+) -> Vec<HistoryEntry> {
+    let timestamp = clock.unix_seconds();
+
+    timings
+        .data
+        .iter()
+        .map(|timing| HistoryEntry::from_timing(timing, timestamp, commit.clone(), machine))
+        .collect()
+}
+
+/// Reads all entries of the history file. Lines that can not be parsed are skipped.
+pub fn read_from_file() -> Vec<HistoryEntry> {
+    fs::read_to_string(HISTORY_FILE_PATH)
+        .map(|s| parse_lines(&s))
+        .unwrap_or_default()
+}
+
+fn parse_lines(s: &str) -> Vec<HistoryEntry> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| {
+            JsonValue::from_str(l)
+                .map_err(|e| e.to_string())
+                .and_then(|json| HistoryEntry::try_from(&json))
+                .map_err(|e| eprintln!("Skipping invalid history entry: {e}"))
+                .ok()
+        })
+        .collect()
+}
+
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let mut commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|o| !o.stdout.is_empty());
+
+    if is_dirty {
+        commit.push_str("-dirty");
+    }
+
+    Some(commit)
+}
+
+/// Identifies the machine a benchmark ran on. Can be overridden with the `AOC_MACHINE_ID` env var.
+fn machine_id() -> String {
+    std::env::var("AOC_MACHINE_ID")
+        .ok()
+        .or_else(|| {
+            Command::new("hostname")
+                .output()
+                .ok()
+                .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        })
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Prints a trend table and sparklines of the stored benchmarks of a day.
+pub fn print_trend(day: Day, entries: &[HistoryEntry]) {
+    let entries: Vec<&HistoryEntry> = entries.iter().filter(|e| e.day == day).collect();
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+
+    if entries.is_empty() {
+        println!("No benchmark history. Run `cargo time {day} --store` to record one.");
+        return;
+    }
+
+    let rows: Vec<[String; 5]> = entries
+        .iter()
+        .map(|e| {
+            [
+                format_timestamp(e.timestamp),
+                e.commit.clone().unwrap_or_else(|| "-".into()),
+                e.machine.clone(),
+                format_part(e.part_1.as_ref()),
+                format_part(e.part_2.as_ref()),
+            ]
+        })
+        .collect();
+
+    println!(
+        "{}",
+        format_table(
+            &["Date (UTC)", "Commit", "Machine", "Part 1", "Part 2"],
+            &rows
+        )
+    );
+
+    println!();
+
+    for (part, values) in [
+        ("Part 1", part_values(&entries, |e| e.part_1.as_ref())),
+        ("Part 2", part_values(&entries, |e| e.part_2.as_ref())),
+    ] {
+        if let Some(trend) = format_trend(&values) {
+            println!("{part}: {trend}");
+        }
+    }
+}
+
+pub(crate) fn part_values(
+    entries: &[&HistoryEntry],
+    part: impl Fn(&HistoryEntry) -> Option<&PartRecord>,
+) -> Vec<f64> {
+    entries
+        .iter()
+        .filter_map(|e| part(e).map(|p| p.nanos))
+        .collect()
+}
+
+pub(crate) fn format_part(part: Option<&PartRecord>) -> String {
+    part.map_or_else(|| "-".into(), |p| format_nanos(p.nanos))
+}
+
+/// Formats a sparkline of the values followed by the change between the first and last value.
+pub(crate) fn format_trend(values: &[f64]) -> Option<String> {
+    let first = values.first()?;
+    let last = values.last()?;
+    let change = if *first > 0.0 {
+        format!(" ({:+.1}%)", (last - first) / first * 100.0)
+    } else {
+        String::new()
+    };
+
+    Some(format!(
+        "{} {} → {}{change}",
+        sparkline(values),
+        format_nanos(*first),
+        format_nanos(*last)
+    ))
+}
+
+/// Renders the values as a sparkline, scaled between the minimum and maximum value.
+pub fn sparkline(values: &[f64]) -> String {
+    const TICKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    values
+        .iter()
+        .map(|v| {
+            if max - min <= f64::EPSILON {
+                return TICKS[3];
+            }
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let index = ((v - min) / (max - min) * (TICKS.len() - 1) as f64).round() as usize;
+            TICKS[index]
+        })
+        .collect()
+}
+
+/// Formats a unix timestamp as `YYYY-MM-DD HH:MM`.
+pub(crate) fn format_timestamp(timestamp: u64) -> String {
+    let (year, month, day) = civil_from_days(timestamp / 86_400);
+    let seconds = timestamp % 86_400;
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        if let Some(alloc) = &value.alloc {
+            map.insert("alloc".into(), JsonValue::from(alloc));
+        }
+        if let Some(samples) = value.samples {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert("samples".into(), JsonValue::Number(samples as f64));
+        }
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part record to be a JSON object.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected part record to have numeric `nanos`.")?;
+
+        let alloc = json.get("alloc").map(AllocStats::try_from).transpose()?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64);
+
+        Ok(PartRecord {
+            nanos,
+            alloc,
+            samples,
+        })
+    }
+}
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("machine".into(), JsonValue::String(value.machine.clone()));
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected history.timestamp to be a number.")?;
+
+        let commit = json.get("commit").and_then(|v| v.get::<String>()).cloned();
+
+        let machine = json
+            .get("machine")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .ok_or("Expected history.machine to be a string.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected history.day to be a Day struct.")?;
+
+        let part = |key: &str| {
+            json.get(key)
+                .filter(|v| !v.is_null())
+                .map(PartRecord::try_from)
+                .transpose()
+        };
+
+        let total_nanos = json
+            .get("total_nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected history.total_nanos to be a number.")?;
+
+        Ok(HistoryEntry {
+            timestamp,
+            commit,
+            machine,
+            day,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+            total_nanos,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{entries_at, format_timestamp, format_trend, parse_lines, sparkline, HistoryEntry};
+    use crate::{
+        day,
+        template::{
+            clock::FixedClock,
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn get_mock_timing() -> Timing {
+        Timing {
+            part_1: Some(7_300_000_f64),
+            part_1_samples: Some(137),
+            total_nanos: 7_300_000_f64,
+            ..Timing::new(day!(4))
+        }
+    }
+
+    #[test]
+    fn creates_entries_from_timings() {
+        let entry = HistoryEntry::from_timing(&get_mock_timing(), 0, Some("abc1234".into()), "box");
+        let part_1 = entry.part_1.unwrap();
+        assert_eq!(part_1.nanos, 7_300_000_f64);
+        assert_eq!(part_1.samples, Some(137));
+        assert_eq!(entry.part_2, None);
+    }
+
+    #[test]
+    fn takes_timestamps_from_the_clock() {
+        let timings = Timings {
+            data: vec![get_mock_timing()],
+        };
+        let clock = FixedClock::from_unix_seconds(1733720000);
+        let entries = entries_at(&timings, &clock, None, "box");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].timestamp, 1733720000);
+    }
+
+    #[test]
+    fn round_trips_json_lines() {
+        let entry = HistoryEntry::from_timing(&get_mock_timing(), 1733720000, None, "box");
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        let parsed = parse_lines(&format!("{line}\nnot json\n\n{line}\n"));
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0], entry);
+    }
+
+    #[test]
+    fn renders_sparklines() {
+        assert_eq!(sparkline(&[1.0, 2.0, 8.0, 1.0]), "▁▂█▁");
+        assert_eq!(sparkline(&[5.0, 5.0]), "▄▄");
+    }
+
+    #[test]
+    fn formats_trends() {
+        assert_eq!(
+            format_trend(&[2_000_000.0, 1_000_000.0]).unwrap(),
+            "█▁ 2.0ms → 1.0ms (-50.0%)"
+        );
+        assert_eq!(format_trend(&[]), None);
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1733720000), "2024-12-09 04:53");
+        assert_eq!(format_timestamp(951782400), "2000-02-29 00:00");
+    }
+}
@@ -0,0 +1,204 @@
+//! Encrypted copies of the inputs, e.g. `data/inputs/01.txt.enc`, that can be committed to a private repository.
+//! Inputs are encrypted with XChaCha20-Poly1305. The key is read from the `AOC_INPUT_KEY` env var or the
+//! git-ignored `.aoc-input-key` file, see `cargo inputs`.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+
+use crate::template::error::Error;
+
+pub const KEY_ENV: &str = "AOC_INPUT_KEY";
+pub const KEY_FILE: &str = ".aoc-input-key";
+
+/// Start of every encrypted file, followed by the nonce and the ciphertext.
+const MAGIC: &[u8] = b"aoc-input-v1\n";
+const NONCE_LEN: usize = 24;
+
+/// A 256-bit key, written as 64 hex digits.
+pub struct InputKey(Key);
+
+impl InputKey {
+    pub fn generate() -> Self {
+        Self(XChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let s = s.trim();
+        let invalid = || {
+            Error::Usage(format!(
+                "the input key must be 64 hex digits, see {KEY_ENV}."
+            ))
+        };
+
+        if s.len() != 64 || !s.is_ascii() {
+            return Err(invalid());
+        }
+
+        let mut key = Key::default();
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+        }
+
+        Ok(Self(key))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    /// Reads the key from `AOC_INPUT_KEY`, or from `.aoc-input-key` if the env var is not set.
+    /// Returns `None` if there is no key.
+    pub fn load() -> Result<Option<Self>, Error> {
+        // an empty variable counts as unset, e.g. a CI secret that is not available in forks.
+        if let Some(key) = env::var(KEY_ENV).ok().filter(|key| !key.trim().is_empty()) {
+            return Self::parse(&key).map(Some);
+        }
+
+        match fs::read_to_string(KEY_FILE) {
+            Ok(key) => Self::parse(&key).map(Some),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(Error::io(format!("Failed to read \"{KEY_FILE}\""), e)),
+        }
+    }
+
+    /// Encrypts the contents of a file. The name of the file is authenticated as well, so encrypted
+    /// files cannot be swapped between days.
+    pub fn encrypt(&self, name: &str, contents: &str) -> Vec<u8> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let payload = Payload {
+            msg: contents.as_bytes(),
+            aad: name.as_bytes(),
+        };
+        let ciphertext = XChaCha20Poly1305::new(&self.0)
+            .encrypt(&nonce, payload)
+            .expect("encrypting in memory cannot fail");
+
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn decrypt(&self, name: &str, data: &[u8]) -> Result<String, Error> {
+        let damaged = || {
+            Error::Usage(format!(
+                "could not decrypt \"{name}.enc\", the key is wrong or the file is damaged."
+            ))
+        };
+
+        let data = data.strip_prefix(MAGIC).ok_or_else(damaged)?;
+        if data.len() < NONCE_LEN {
+            return Err(damaged());
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+
+        let payload = Payload {
+            msg: ciphertext,
+            aad: name.as_bytes(),
+        };
+        let contents = XChaCha20Poly1305::new(&self.0)
+            .decrypt(XNonce::from_slice(nonce), payload)
+            .map_err(|_| damaged())?;
+
+        String::from_utf8(contents).map_err(|_| damaged())
+    }
+}
+
+/// Path of the encrypted copy of a file, e.g. `data/inputs/01.txt.enc`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".enc");
+    PathBuf::from(path)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Reads a file, or decrypts its encrypted copy if the file is missing or empty.
+/// A scaffolded day has an empty input file, which must not hide a committed encrypted input.
+pub fn read(path: &Path) -> Result<String, Error> {
+    let display = path.display();
+
+    match fs::read_to_string(path) {
+        Ok(contents) if !contents.is_empty() => return Ok(contents),
+        Ok(contents) if !encrypted_path(path).exists() => return Ok(contents),
+        Ok(_) => {}
+        Err(e) if e.kind() != io::ErrorKind::NotFound => {
+            return Err(Error::io(format!("Failed to read \"{display}\""), e))
+        }
+        Err(_) if !encrypted_path(path).exists() => {
+            return Err(Error::NotFound(format!("\"{display}\" does not exist.")))
+        }
+        Err(_) => {}
+    }
+
+    let data = fs::read(encrypted_path(path))
+        .map_err(|e| Error::io(format!("Failed to read \"{display}.enc\""), e))?;
+    let key = InputKey::load()?.ok_or_else(|| {
+        Error::Usage(format!(
+            "\"{display}\" is only stored encrypted, set {KEY_ENV} or create \"{KEY_FILE}\" to decrypt it."
+        ))
+    })?;
+
+    key.decrypt(&file_name(path), &data)
+}
+
+/// `true` if a file can be read with [`read`]: it has contents, or it has an encrypted copy and there is a key.
+pub fn is_readable(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+        || (encrypted_path(path).exists() && matches!(InputKey::load(), Ok(Some(_))))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{encrypted_path, InputKey};
+
+    #[test]
+    fn encrypts_and_decrypts() {
+        let key = InputKey::generate();
+        let data = key.encrypt("01.txt", "3   4\n4   3\n");
+
+        assert_eq!(key.decrypt("01.txt", &data).unwrap(), "3   4\n4   3\n");
+        // the name is authenticated.
+        assert!(key.decrypt("02.txt", &data).is_err());
+        assert!(InputKey::generate().decrypt("01.txt", &data).is_err());
+        assert!(key.decrypt("01.txt", &data[..20]).is_err());
+        // every encryption uses a new nonce.
+        assert_ne!(key.encrypt("01.txt", "1"), key.encrypt("01.txt", "1"));
+    }
+
+    #[test]
+    fn parses_keys() {
+        let key = InputKey::generate();
+        assert_eq!(
+            InputKey::parse(&key.to_hex()).unwrap().to_hex(),
+            key.to_hex()
+        );
+        assert_eq!(
+            InputKey::parse(&format!("{}\n", key.to_hex().to_uppercase()))
+                .unwrap()
+                .to_hex(),
+            key.to_hex()
+        );
+        assert!(InputKey::parse("").is_err());
+        assert!(InputKey::parse(&"g".repeat(64)).is_err());
+        assert!(InputKey::parse(&"ä".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn appends_extension() {
+        assert_eq!(
+            encrypted_path(Path::new("data/inputs/01.txt")),
+            Path::new("data/inputs/01.txt.enc")
+        );
+    }
+}
@@ -0,0 +1,499 @@
+//! Private leaderboard viewer.
+//! The leaderboard JSON is fetched from the Advent of Code website with the session cookie
+//! and cached in `data/leaderboards/`, as the site asks to not request it more than once every 15 minutes.
+
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+    str::FromStr,
+    time::Duration,
+};
+use tinyjson::JsonValue;
+
+use crate::template::clock;
+use crate::template::table::format_table;
+use crate::template::{event_days, Day, ANSI_BOLD, ANSI_RESET};
+
+const CACHE_DIR: &str = "data/leaderboards";
+const CACHE_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+/// Identifies requests to the website, the site asks automated tools to link to their source.
+/// Can be replaced with the `AOC_USER_AGENT` env var, e.g. to add contact details.
+const USER_AGENT: &str = "github.com/0x3444ac53/AOC-2024 leaderboard";
+
+/// Completion timestamps of both parts of a day.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DayProgress {
+    pub part_1: Option<u64>,
+    pub part_2: Option<u64>,
+}
+
+impl DayProgress {
+    pub fn stars(&self) -> u8 {
+        u8::from(self.part_1.is_some()) + u8::from(self.part_2.is_some())
+    }
+}
+
+/// A member of a private leaderboard.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u64,
+    pub last_star_ts: u64,
+    pub days: HashMap<u8, DayProgress>,
+}
+
+impl Member {
+    /// Anonymous members are shown by id, same as on the website.
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+/// A private leaderboard of one event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub event: u16,
+    pub owner_id: u64,
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    /// Members ordered like on the website: by local score, then by who got their last star first.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.iter().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+        members
+    }
+
+    /// The latest day any member has collected a star on.
+    pub fn latest_day(&self) -> Option<Day> {
+        self.members
+            .iter()
+            .flat_map(|m| m.days.keys())
+            .max()
+            .and_then(|day| Day::new(*day))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Unix timestamp at which the puzzle of a day unlocks.
+pub fn unlock_timestamp(year: u16, day: Day) -> u64 {
+    clock::server_midnight(u64::from(year), 12, u64::from(day.into_inner()))
+}
+
+/// Formats a number of seconds as `HH:MM:SS`, hours are not wrapped at 24.
+pub fn format_seconds(seconds: u64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Leaderboard ids are numbers. They end up in the cache path, so nothing else is accepted.
+pub fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn cache_path(year: u16, id: &str) -> String {
+    format!("{CACHE_DIR}/{year}-{id}.json")
+}
+
+fn session_cookie() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session.trim().to_string());
+    }
+
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    let path: PathBuf = [home.as_str(), ".adventofcode.session"].iter().collect();
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn fetch(year: u16, id: &str) -> Result<String, String> {
+    let session = session_cookie()
+        .ok_or("No session cookie found. Set AOC_SESSION or create ~/.adventofcode.session.")?;
+    let url = format!("https://adventofcode.com/{year}/leaderboard/private/view/{id}.json");
+    let user_agent = env::var("AOC_USER_AGENT").unwrap_or_else(|_| USER_AGENT.into());
+
+    // the cookie is passed as config on stdin, arguments can be read by every user of the machine.
+    let mut child = Command::new("curl")
+        .args(["--silent", "--fail", "--location", "--config", "-"])
+        .args(["--user-agent", &user_agent])
+        .arg(&url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|_| "curl could not be called.".to_string())?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "cookie = \"session={session}\"")
+            .map_err(|_| "Could not pass the session cookie to curl.".to_string())?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|_| "curl could not be called.".to_string())?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Could not fetch leaderboard from {url}. Is the session cookie still valid?"
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn is_fresh(path: &str) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < CACHE_MAX_AGE)
+}
+
+/// Loads a private leaderboard, from the cache if it is recent enough or `offline` is set,
+/// from the website otherwise. Falls back to a stale cache if fetching fails.
+pub fn load(year: u16, id: &str, offline: bool) -> Result<Leaderboard, String> {
+    if !is_valid_id(id) {
+        return Err(format!("Invalid leaderboard id `{id}`, expected a number."));
+    }
+    let path = cache_path(year, id);
+
+    let json = if offline || is_fresh(&path) {
+        fs::read_to_string(&path).map_err(|_| {
+            format!("No cached leaderboard at \"{path}\". Run without `--offline` to fetch it.")
+        })?
+    } else {
+        match fetch(year, id) {
+            Ok(json) => {
+                let _ = fs::create_dir_all(CACHE_DIR);
+                if let Err(e) = fs::write(&path, &json) {
+                    eprintln!("Failed to cache leaderboard: {e}");
+                }
+                json
+            }
+            Err(e) => {
+                let cached = fs::read_to_string(&path).map_err(|_| e.clone())?;
+                eprintln!("{e} Showing cached leaderboard.");
+                cached
+            }
+        }
+    };
+
+    Leaderboard::from_str(&json)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Prints the ranking with stars per day, followed by completion times of `day`.
+pub fn print(leaderboard: &Leaderboard, day: Option<Day>) {
+    let now = clock::from_env().unix_seconds();
+
+    println!(
+        "{ANSI_BOLD}Private leaderboard {}{ANSI_RESET}",
+        leaderboard.event
+    );
+    println!("{}", ranking_table(leaderboard, now));
+
+    if let Some(day) = day.or_else(|| leaderboard.latest_day()) {
+        println!();
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("{}", day_table(leaderboard, day));
+    }
+}
+
+/// One character per day that is unlocked at `now`: `*` for both stars, `+` for one, `.` for none.
+fn star_string(leaderboard: &Leaderboard, member: &Member, now: u64) -> String {
+    (1..=event_days(leaderboard.event))
+        .filter_map(Day::new)
+        .take_while(|day| unlock_timestamp(leaderboard.event, *day) <= now)
+        .map(|day| {
+            match member
+                .days
+                .get(&day.into_inner())
+                .map_or(0, DayProgress::stars)
+            {
+                2 => '*',
+                1 => '+',
+                _ => '.',
+            }
+        })
+        .collect()
+}
+
+fn ranking_table(leaderboard: &Leaderboard, now: u64) -> String {
+    let rows: Vec<Vec<String>> = leaderboard
+        .ranked()
+        .iter()
+        .enumerate()
+        .map(|(i, member)| {
+            vec![
+                format!("{})", i + 1),
+                member.local_score.to_string(),
+                member.stars.to_string(),
+                star_string(leaderboard, member, now),
+                member.display_name(),
+            ]
+        })
+        .collect();
+
+    format_table(&["Rank", "Score", "Stars", "Days", "Name"], &rows)
+}
+
+fn day_table(leaderboard: &Leaderboard, day: Day) -> String {
+    let unlock = unlock_timestamp(leaderboard.event, day);
+    let since_unlock = |ts: Option<u64>| ts.map(|ts| ts.saturating_sub(unlock));
+    let format = |seconds: Option<u64>| seconds.map_or_else(|| "-".into(), format_seconds);
+
+    let mut members: Vec<(&Member, DayProgress)> = leaderboard
+        .members
+        .iter()
+        .filter_map(|m| Some((m, *m.days.get(&day.into_inner())?)))
+        .collect();
+
+    // fastest to both stars first, then everyone with a single star.
+    members.sort_by_key(|(m, progress)| {
+        (
+            progress.part_2.is_none(),
+            progress.part_2.or(progress.part_1),
+            m.id,
+        )
+    });
+
+    let rows: Vec<Vec<String>> = members
+        .iter()
+        .map(|(member, progress)| {
+            let delta = progress
+                .part_2
+                .zip(progress.part_1)
+                .map(|(part_2, part_1)| part_2.saturating_sub(part_1));
+
+            vec![
+                member.display_name(),
+                format(since_unlock(progress.part_1)),
+                format(since_unlock(progress.part_2)),
+                format(delta),
+            ]
+        })
+        .collect();
+
+    if rows.is_empty() {
+        return "No stars yet.".into();
+    }
+
+    format_table(&["Name", "Part 1", "Part 2", "Delta"], &rows)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Leaderboard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).map_err(|_| "Leaderboard is not valid JSON.")?;
+        Leaderboard::try_from(&json)
+    }
+}
+
+/// Reads a number that the website encodes either as a JSON number or as a string.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn number(json: &HashMap<String, JsonValue>, key: &str) -> Option<u64> {
+    match json.get(key)? {
+        JsonValue::Number(n) => Some(*n as u64),
+        JsonValue::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+impl TryFrom<&JsonValue> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected leaderboard to be a JSON object.")?;
+
+        let event = number(json, "event")
+            .and_then(|x| u16::try_from(x).ok())
+            .ok_or("Expected leaderboard.event to be a year.")?;
+
+        let owner_id = number(json, "owner_id").ok_or("Expected leaderboard.owner_id.")?;
+
+        let mut members = json
+            .get("members")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected leaderboard.members to be an object.")?
+            .values()
+            .map(Member::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        members.sort_by_key(|m| m.id);
+
+        Ok(Leaderboard {
+            event,
+            owner_id,
+            members,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Member {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected member to be a JSON object.")?;
+
+        let id = number(json, "id").ok_or("Expected member.id.")?;
+        let name = json.get("name").and_then(|v| v.get::<String>()).cloned();
+
+        let mut days = HashMap::new();
+        if let Some(completion) = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+        {
+            for (day, parts) in completion {
+                let day: u8 = day
+                    .parse()
+                    .map_err(|_| format!("Expected completion day, found `{day}`."))?;
+                let parts = parts
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("Expected completion_day_level entries to be objects.")?;
+                let star = |part: &str| {
+                    parts
+                        .get(part)
+                        .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+                        .and_then(|v| number(v, "get_star_ts"))
+                };
+
+                days.insert(
+                    day,
+                    DayProgress {
+                        part_1: star("1"),
+                        part_2: star("2"),
+                    },
+                );
+            }
+        }
+
+        Ok(Member {
+            id,
+            name,
+            local_score: number(json, "local_score").unwrap_or(0),
+            stars: number(json, "stars").unwrap_or(0),
+            last_star_ts: number(json, "last_star_ts").unwrap_or(0),
+            days,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{
+        day_table, format_seconds, is_valid_id, ranking_table, unlock_timestamp, Leaderboard,
+        ANSI_RESET,
+    };
+    use crate::day;
+    use crate::template::ANSI_ITALIC;
+
+    const FIXTURE: &str = include_str!("fixtures/leaderboard.json");
+
+    fn strip_ansi(s: &str) -> String {
+        s.replace(ANSI_ITALIC, "").replace(ANSI_RESET, "")
+    }
+
+    #[test]
+    fn parses_fixture() {
+        let leaderboard = Leaderboard::from_str(FIXTURE).unwrap();
+        assert_eq!(leaderboard.event, 2024);
+        assert_eq!(leaderboard.owner_id, 1001);
+        assert_eq!(leaderboard.members.len(), 3);
+        assert_eq!(leaderboard.latest_day(), Some(day!(3)));
+
+        let anonymous = &leaderboard.members[2];
+        assert_eq!(anonymous.display_name(), "(anonymous user #1003)");
+        assert_eq!(anonymous.days[&2].part_2, None);
+    }
+
+    #[test]
+    fn computes_unlock_timestamps() {
+        // 2024-12-01 05:00:00 UTC
+        assert_eq!(unlock_timestamp(2024, day!(1)), 1733029200);
+        assert_eq!(unlock_timestamp(2024, day!(25)), 1733029200 + 24 * 86_400);
+    }
+
+    #[test]
+    fn validates_ids() {
+        assert_eq!(is_valid_id("123456"), true);
+        assert_eq!(is_valid_id(""), false);
+        assert_eq!(is_valid_id("../../etc/passwd"), false);
+        assert_eq!(is_valid_id("12/34"), false);
+        assert_eq!(is_valid_id("１２"), false);
+    }
+
+    #[test]
+    fn formats_seconds() {
+        assert_eq!(format_seconds(0), "00:00:00");
+        assert_eq!(format_seconds(3723), "01:02:03");
+        assert_eq!(format_seconds(90_000), "25:00:00");
+    }
+
+    #[test]
+    fn ranks_members() {
+        let leaderboard = Leaderboard::from_str(FIXTURE).unwrap();
+        let now = unlock_timestamp(2024, day!(3)) + 60;
+        let table = strip_ansi(&ranking_table(&leaderboard, now));
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines[0], "Rank  Score  Stars  Days  Name");
+        assert_eq!(lines[1], "1)    30     6      ***   Alice");
+        assert_eq!(lines[2], "2)    24     5      **+   Bob");
+        assert_eq!(lines[3], "3)    5      3      *+.   (anonymous user #1003)");
+    }
+
+    #[test]
+    fn shows_completion_times_relative_to_unlock() {
+        let leaderboard = Leaderboard::from_str(FIXTURE).unwrap();
+        let table = strip_ansi(&day_table(&leaderboard, day!(2)));
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "Name                    Part 1    Part 2    Delta"
+        );
+        assert_eq!(
+            lines[1],
+            "Alice                   00:05:00  00:08:20  00:03:20"
+        );
+        assert_eq!(
+            lines[2],
+            "Bob                     00:10:00  00:25:00  00:15:00"
+        );
+        assert_eq!(lines[3], "(anonymous user #1003)  26:00:00  -         -");
+    }
+}
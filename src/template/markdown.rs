@@ -0,0 +1,213 @@
+//! Terminal renderer for the markdown of puzzle descriptions.
+//! Supports the subset aoc-cli writes: headings, emphasis, inline code, code blocks, lists and links.
+//! Prose is wrapped to the terminal width, code blocks are kept as they are.
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_CODE: &str = "\x1b[36m";
+
+/// Renders markdown with ANSI styles: headings and emphasis in bold, code in color, code blocks indented.
+/// Paragraphs and list items are wrapped to `width` columns.
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut lines = vec![];
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            lines.push(format!("    {ANSI_CODE}{line}{ANSI_RESET}"));
+        } else if line.starts_with('#') {
+            let heading = line.trim_start_matches('#').trim();
+            lines.push(format!("{ANSI_BOLD}{heading}{ANSI_RESET}"));
+        } else if let Some(item) = line
+            .trim_start()
+            .strip_prefix("- ")
+            .or_else(|| line.trim_start().strip_prefix("* "))
+        {
+            let wrapped = wrap(&render_inline(item, ""), width.saturating_sub(4));
+            for (i, item_line) in wrapped.iter().enumerate() {
+                let bullet = if i == 0 { "  • " } else { "    " };
+                lines.push(format!("{bullet}{item_line}"));
+            }
+        } else {
+            lines.extend(wrap(&render_inline(line, ""), width));
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Wraps styled text at spaces, so that no line is wider than `width` visible characters.
+/// Words that are wider than `width` are kept on a line of their own.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+    let mut line_width = 0;
+
+    for word in text.split(' ') {
+        let word_width = visible_width(word);
+
+        if line_width > 0 && line_width + 1 + word_width > width {
+            lines.push(std::mem::take(&mut line));
+            line_width = 0;
+        }
+
+        if !line.is_empty() {
+            line.push(' ');
+            line_width += 1;
+        }
+
+        line.push_str(word);
+        line_width += word_width;
+    }
+
+    lines.push(line);
+    lines
+}
+
+/// Number of characters of a string that are visible in the terminal, without ANSI escape sequences.
+fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+
+    for c in text.chars() {
+        match (in_escape, c) {
+            (false, '\x1b') => in_escape = true,
+            (true, 'm') => in_escape = false,
+            (false, _) => width += 1,
+            (true, _) => {}
+        }
+    }
+
+    width
+}
+
+/// Renders the inline markup of a line. `active` holds the styles of the enclosing spans,
+/// they are applied again after a nested span resets the style.
+fn render_inline(text: &str, active: &str) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let span = match c {
+            '`' => rest[1..].find('`').map(|end| {
+                let code = &rest[1..=end];
+                let inner = code.trim_matches('*');
+                let style = if inner.len() < code.len() {
+                    format!("{ANSI_BOLD}{ANSI_CODE}")
+                } else {
+                    ANSI_CODE.to_string()
+                };
+                (format!("{style}{inner}{ANSI_RESET}{active}"), end + 2)
+            }),
+            '*' => {
+                let marker = if rest.starts_with("**") {
+                    &rest[..2]
+                } else {
+                    &rest[..1]
+                };
+                rest[marker.len()..]
+                    .find(marker)
+                    .filter(|end| *end > 0)
+                    .map(|end| {
+                        let inner = &rest[marker.len()..marker.len() + end];
+                        let style = format!("{active}{ANSI_BOLD}");
+                        (
+                            format!(
+                                "{ANSI_BOLD}{}{ANSI_RESET}{active}",
+                                render_inline(inner, &style)
+                            ),
+                            end + 2 * marker.len(),
+                        )
+                    })
+            }
+            '[' => rest.find("](").and_then(|middle| {
+                let end = middle + rest[middle..].find(')')?;
+                Some((render_inline(&rest[1..middle], active), end + 1))
+            }),
+            _ => None,
+        };
+
+        match span {
+            Some((rendered, len)) => {
+                result.push_str(&rendered);
+                rest = &rest[len..];
+            }
+            None => {
+                result.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    result
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, visible_width, wrap, ANSI_CODE};
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+    #[test]
+    fn renders_headings_and_code_blocks() {
+        assert_eq!(
+            render(
+                "## --- Day 1: A ---\n\nFor example:\n\n```\n1 2\n```\n",
+                80
+            ),
+            format!(
+                "{ANSI_BOLD}--- Day 1: A ---{ANSI_RESET}\n\nFor example:\n\n    {ANSI_CODE}1 2{ANSI_RESET}"
+            )
+        );
+    }
+
+    #[test]
+    fn renders_inline_markup() {
+        assert_eq!(
+            render("a total of `*11*`, *not* `12`", 80),
+            format!(
+                "a total of {ANSI_BOLD}{ANSI_CODE}11{ANSI_RESET}, {ANSI_BOLD}not{ANSI_RESET} {ANSI_CODE}12{ANSI_RESET}"
+            )
+        );
+        assert_eq!(
+            render("see [the list](https://example.com) of 2 * 3", 80),
+            "see the list of 2 * 3"
+        );
+        assert_eq!(
+            render("*a `b` c*", 80),
+            format!("{ANSI_BOLD}a {ANSI_CODE}b{ANSI_RESET}{ANSI_BOLD} c{ANSI_RESET}")
+        );
+    }
+
+    #[test]
+    fn wraps_to_width() {
+        assert_eq!(
+            wrap("the quick brown fox jumps", 10),
+            vec!["the quick", "brown fox", "jumps"]
+        );
+        assert_eq!(wrap("a verylongword b", 5), vec!["a", "verylongword", "b"]);
+        assert_eq!(wrap("", 5), vec![""]);
+
+        let styled = format!("{ANSI_BOLD}aaaa{ANSI_RESET} bbbb");
+        assert_eq!(visible_width(&styled), 9);
+        assert_eq!(wrap(&styled, 9), vec![styled.clone()]);
+    }
+
+    #[test]
+    fn renders_lists() {
+        assert_eq!(
+            render("- one two three\n- four", 12),
+            "  • one two\n    three\n  • four"
+        );
+        assert_eq!(
+            render("```\n- not a list item that is wrapped\n```", 12),
+            format!("    {ANSI_CODE}- not a list item that is wrapped{ANSI_RESET}")
+        );
+    }
+}
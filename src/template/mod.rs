@@ -1,27 +1,50 @@
 use std::{env, fs};
 
+pub mod allocations;
+pub mod answers;
 pub mod aoc_cli;
+pub mod clock;
 pub mod commands;
+pub mod compare;
+pub mod error;
+pub mod fuzz;
+pub mod report;
 pub mod runner;
 
 pub use day::*;
+pub use day_set::{DaySet, DaySetKeywords};
 
+mod audit;
 mod day;
+mod day_set;
+mod example_tests;
+mod examples;
+mod history;
+mod inputs;
+mod leaderboard;
+mod markdown;
+mod puzzle;
 mod readme_benchmarks;
 mod run_multi;
+mod status;
+mod table;
+mod templates;
+mod terminal;
 mod timings;
 
+include!(concat!(env!("OUT_DIR"), "/inputs.rs"));
+
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// If the file is missing, its encrypted copy is decrypted instead, e.g. `data/inputs/01.txt.enc`.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    inputs::read(&filepath).unwrap_or_else(|e| panic!("could not open input file: {e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
@@ -39,19 +62,38 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Additional options can be registered after a `;`:
+///  - `fuzz(generator, 2 => part_two_naive)` registers a reference implementation for a part and an
+///    input generator used by `cargo fuzz-check`.
+///  - `alternatives(1 => [part_one_naive, part_one_simd])` registers named alternative implementations
+///    of a part that `cargo bench-compare` benches against the main implementation.
+///  - `parse(parse_input)` registers the input parser shared by both parts so that its runtime is reported separately.
+///  - `input_tests()` generates a test that runs the parts on the real input and compares their results to the
+///    answers accepted by the website, see `data/answers.json`. The test is ignored if the input file is missing,
+///    or if it is encrypted and there is no key.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:tt) => {
+        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2];);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($day:tt, 1) => {
+        $crate::solution!(@impl $day, [part_one, 1];);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:tt, 2) => {
+        $crate::solution!(@impl $day, [part_two, 2];);
+    };
+    ($day:tt; $($options:tt)*) => {
+        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]; $($options)*);
+    };
+    ($day:tt, 1; $($options:tt)*) => {
+        $crate::solution!(@impl $day, [part_one, 1]; $($options)*);
+    };
+    ($day:tt, 2; $($options:tt)*) => {
+        $crate::solution!(@impl $day, [part_two, 2]; $($options)*);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:tt, $( [$func:expr, $part:expr] )*; $( $option:ident($($args:tt)*) ),* $(,)?) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +101,105 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(feature = "alloc-stats")]
+        #[global_allocator]
+        static ALLOC: $crate::template::allocations::CountingAlloc =
+            $crate::template::allocations::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+
+            #[allow(unused_mut)]
+            let mut registry = Registry::default();
+            $( $crate::solution!(@option registry, $option($($args)*)); )*
+
+            let run = || -> Result<(), $crate::template::error::Error> {
+                if $crate::template::fuzz::is_requested() {
+                    return $crate::template::fuzz::run(DAY, &registry.fuzz);
+                }
+
+                if $crate::template::compare::is_requested() {
+                    let main = [$( $crate::template::compare::Implementation::new($part, stringify!($func), $func) ),*];
+                    return $crate::template::compare::run(DAY, &main, &registry.alternatives);
+                }
+
+                let input = read_input(DAY)?;
+
+                if let Some(parse) = &registry.parse {
+                    run_parse(parse.as_ref(), &input);
+                }
+
+                $( run_part($func, &input, DAY, $part)?; )*
+                Ok(())
+            };
+
+            if let Err(e) = run() {
+                eprintln!("{e}");
+                std::process::exit(e.exit_code());
+            }
         }
+
+        $crate::solution!(@items $day, [$( [$func, $part] )*]; $( $option($($args)*) ),*);
+    };
+
+    (@items $day:tt, $parts:tt; $( $option:ident($($args:tt)*) ),*) => {
+        $( $crate::solution!(@item $day, $parts, $option($($args)*)); )*
+    };
+
+    (@item $day:tt, [$( [$func:expr, $part:expr] )*], input_tests()) => {
+        $crate::__if_input! { $day, {
+            #[cfg(test)]
+            mod input_tests {
+                use super::*;
+
+                #[test]
+                fn real_input() {
+                    $crate::template::runner::check_input_answers(DAY, &[$( ($part, &|input: &str| $func(input).map(|x| x.to_string())) ),*]);
+                }
+            }
+        } else {
+            #[cfg(test)]
+            mod input_tests {
+                #[test]
+                #[ignore = "the input of this day is missing, or it is encrypted and there is no key"]
+                fn real_input() {}
+            }
+        } }
     };
+
+    (@item $day:tt, $parts:tt, $option:ident($($args:tt)*)) => {};
+
+    (@option $registry:ident, input_tests()) => {};
+
+    (@option $registry:ident, fuzz($generator:expr, $( $part:tt => $reference:expr ),+ $(,)?)) => {
+        $(
+            $registry.fuzz.push($crate::template::fuzz::FuzzTarget::new(
+                $part,
+                ($crate::solution!(@part_name $part), $crate::solution!(@part_fn $part)),
+                (stringify!($reference), $reference),
+                $generator,
+            ));
+        )+
+    };
+
+    (@option $registry:ident, alternatives($( $part:tt => [$( $alternative:expr ),+ $(,)?] ),+ $(,)?)) => {
+        $( $(
+            $registry.alternatives.push($crate::template::compare::Implementation::new(
+                $part,
+                stringify!($alternative),
+                $alternative,
+            ));
+        )+ )+
+    };
+
+    (@option $registry:ident, parse($parser:expr)) => {
+        $registry.parse = Some(Box::new(|input: &str| {
+            std::hint::black_box($parser(input));
+        }));
+    };
+
+    (@part_fn 1) => { part_one };
+    (@part_fn 2) => { part_two };
+    (@part_name 1) => { "part_one" };
+    (@part_name 2) => { "part_two" };
 }
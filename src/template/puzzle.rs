@@ -0,0 +1,53 @@
+//! Local copies of puzzle descriptions, as downloaded by aoc-cli to `data/puzzles/`.
+
+use crate::template::Day;
+
+const PART_TWO_HEADING: &str = "--- Part Two ---";
+
+pub fn path(day: Day) -> String {
+    format!("data/puzzles/{day}.md")
+}
+
+/// Splits a description into part one and, if it was unlocked, part two.
+/// Part two starts at the line with its heading.
+pub fn split_parts(puzzle: &str) -> (&str, Option<&str>) {
+    match puzzle.find(PART_TWO_HEADING) {
+        Some(index) => {
+            let start = puzzle[..index].rfind('\n').map_or(0, |i| i + 1);
+            (&puzzle[..start], Some(&puzzle[start..]))
+        }
+        None => (puzzle, None),
+    }
+}
+
+/// Returns the section of a part of the description, `None` if it is not present.
+pub fn part(puzzle: &str, part: u8) -> Option<&str> {
+    let (part_1, part_2) = split_parts(puzzle);
+    match part {
+        1 => Some(part_1),
+        2 => part_2,
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{part, split_parts};
+
+    #[test]
+    fn splits_parts() {
+        let puzzle = "## --- Day 1: A ---\n\nOne.\n\n## --- Part Two ---\n\nTwo.\n";
+
+        assert_eq!(
+            split_parts(puzzle),
+            (
+                "## --- Day 1: A ---\n\nOne.\n\n",
+                Some("## --- Part Two ---\n\nTwo.\n")
+            )
+        );
+        assert_eq!(part("## --- Day 1: A ---\n", 2), None);
+        assert_eq!(part(puzzle, 3), None);
+    }
+}
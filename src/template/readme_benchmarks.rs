@@ -1,28 +1,149 @@
-/// Module that updates the readme me with timing information.
-/// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+//! Module that updates the readme me with timing information.
+//! The approach taken is similar to how `aoc-readme-stars` handles this.
+//! Columns, order and the optional chart of the table are configured via env vars, see [`TableOptions::from_env`].
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use std::{collections::HashMap, env, fs, str::FromStr};
+
+use crate::template::allocations::AllocStats;
+use crate::template::answers::Answers;
+use crate::template::error::Error;
+use crate::template::timings::{format_nanos, Timing, Timings};
+use crate::template::{all_days, Day};
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static PROGRESS_MARKER: &str = "<!--- progress table --->";
+static CHART_PATH: &str = ".assets/benchmarks.svg";
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    Parser(String),
-    IO(io::Error),
+pub struct TablePosition {
+    pos_start: usize,
+    pos_end: usize,
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+/// A column of the benchmark table, in addition to the day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// Time of the parse function, omitted if no day registers one.
+    Parse,
+    Part1,
+    Part2,
+    /// Sum of both parts.
+    Total,
+    /// Allocation stats of both parts, omitted if none were recorded.
+    Allocations,
+    /// Number of benchmark samples of both parts.
+    Samples,
+    /// Share of the day in the total runtime.
+    Share,
+}
+
+impl Column {
+    pub(crate) fn title(self) -> &'static str {
+        match self {
+            Column::Parse => "Parse",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::Total => "Total",
+            Column::Allocations => "Allocations (Part 1 / Part 2)",
+            Column::Samples => "Samples (Part 1 / Part 2)",
+            Column::Share => "Share",
+        }
     }
 }
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "parse" => Ok(Column::Parse),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "total" => Ok(Column::Total),
+            "allocations" => Ok(Column::Allocations),
+            "samples" => Ok(Column::Samples),
+            "share" => Ok(Column::Share),
+            other => Err(format!(
+                "unknown benchmark column `{other}`, expected one of: parse, part_1, part_2, total, allocations, samples, share."
+            )),
+        }
+    }
+}
+
+/// The key the rows of the benchmark table are sorted by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    Day,
+    Total,
+}
+
+/// Layout of the benchmark table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableOptions {
+    pub columns: Vec<Column>,
+    pub sort_by: SortKey,
+    pub descending: bool,
+    /// Render a bar chart to `.assets/benchmarks.svg` and link it below the table.
+    pub chart: bool,
+}
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        Self {
+            columns: vec![
+                Column::Parse,
+                Column::Part1,
+                Column::Part2,
+                Column::Allocations,
+            ],
+            sort_by: SortKey::Day,
+            descending: false,
+            chart: false,
+        }
+    }
+}
+
+impl TableOptions {
+    /// Reads the table options from the environment:
+    ///  - `AOC_README_COLUMNS`: comma separated list of columns, e.g. `part_1,part_2,total,share`.
+    ///  - `AOC_README_SORT`: `day` or `total`, prefixed with `-` for descending order.
+    ///  - `AOC_README_CHART`: `true` or `1` to render a bar chart.
+    pub fn from_env() -> Result<Self, Error> {
+        let mut options = Self::default();
+
+        if let Ok(columns) = env::var("AOC_README_COLUMNS") {
+            options.columns = columns
+                .split(',')
+                .filter(|x| !x.trim().is_empty())
+                .map(Column::from_str)
+                .collect::<Result<_, _>>()
+                .map_err(Error::Usage)?;
+        }
+
+        if let Ok(sort) = env::var("AOC_README_SORT") {
+            let sort = sort.trim();
+            let (key, descending) = match sort.strip_prefix('-') {
+                Some(key) => (key, true),
+                None => (sort, false),
+            };
+
+            options.sort_by = match key {
+                "day" => SortKey::Day,
+                "total" => SortKey::Total,
+                other => {
+                    return Err(Error::Usage(format!(
+                        "unknown benchmark sort key `{other}`, expected `day` or `total`."
+                    )))
+                }
+            };
+            options.descending = descending;
+        }
+
+        if let Ok(chart) = env::var("AOC_README_CHART") {
+            options.chart = matches!(chart.trim(), "1" | "true");
+        }
+
+        Ok(options)
+    }
 }
 
 #[must_use]
@@ -31,97 +152,350 @@ pub fn get_path_for_bin(day: Day) -> String {
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+    locate_section(readme, MARKER)
+}
+
+fn locate_section(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Usage(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+    let pos_start = matches.first().map(|m| m.0).ok_or_else(|| {
+        Error::Usage(format!(
+            "Could not find the start of the table in README.md, it is marked with `{marker}`."
+        ))
+    })?;
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+    let pos_end = matches.last().map(|m| m.0 + m.1.len()).ok_or_else(|| {
+        Error::Usage(format!(
+            "Could not find the end of the table in README.md, it is marked with `{marker}`."
+        ))
+    })?;
 
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+pub(crate) fn sorted_timings(timings: &Timings, options: &TableOptions) -> Vec<Timing> {
+    let mut data = timings.data.clone();
+
+    match options.sort_by {
+        SortKey::Day => data.sort_by_key(|t| t.day),
+        SortKey::Total => data.sort_by(|a, b| a.total_nanos.total_cmp(&b.total_nanos)),
+    }
+
+    if options.descending {
+        data.reverse();
+    }
+
+    data
+}
+
+/// The configured columns, without the optional ones that no day has data for.
+pub(crate) fn visible_columns(timings: &Timings, options: &TableOptions) -> Vec<Column> {
+    options
+        .columns
+        .iter()
+        .copied()
+        .filter(|column| match column {
+            Column::Parse => timings.has_parse_times(),
+            Column::Allocations => timings.has_alloc_stats(),
+            _ => true,
+        })
+        .collect()
+}
+
+/// The values shown in a cell, one per part for columns that cover both parts.
+pub(crate) fn cell_values(column: Column, timing: &Timing, total_nanos: f64) -> Vec<String> {
+    let format = |x: Option<String>| x.unwrap_or_else(|| "-".into());
+
+    match column {
+        Column::Parse => vec![format(timing.parse.map(format_nanos))],
+        Column::Part1 => vec![format(timing.part_1.map(format_nanos))],
+        Column::Part2 => vec![format(timing.part_2.map(format_nanos))],
+        Column::Total => vec![format_nanos(timing.total_nanos)],
+        Column::Allocations => vec![
+            format(timing.part_1_alloc.map(|a: AllocStats| a.summary())),
+            format(timing.part_2_alloc.map(|a: AllocStats| a.summary())),
+        ],
+        Column::Samples => vec![
+            format(timing.part_1_samples.map(|x| x.to_string())),
+            format(timing.part_2_samples.map(|x| x.to_string())),
+        ],
+        Column::Share if total_nanos > 0.0 => {
+            vec![format!("{:.1}%", timing.total_nanos / total_nanos * 100.0)]
+        }
+        Column::Share => vec!["-".into()],
+    }
+}
+
+fn format_cell(column: Column, timing: &Timing, total_nanos: f64) -> String {
+    let values = cell_values(column, timing, total_nanos);
+
+    match column {
+        Column::Share => values.join(" / "),
+        _ => values
+            .iter()
+            .map(|x| format!("`{x}`"))
+            .collect::<Vec<_>>()
+            .join(" / "),
+    }
+}
+
+/// Header, alignment and one row per day of the markdown benchmark table.
+pub(crate) fn table_lines(timings: &Timings, options: &TableOptions) -> Vec<String> {
+    let columns = visible_columns(timings, options);
+
+    let titles: Vec<&str> = std::iter::once("Day")
+        .chain(columns.iter().map(|c| c.title()))
+        .collect();
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| {} |", titles.join(" | ")),
+        format!("|{}", " :---: |".repeat(titles.len())),
     ];
 
-    for timing in timings.data {
+    let total_nanos: f64 = timings.data.iter().map(|t| t.total_nanos).sum();
+
+    for timing in sorted_timings(timings, options) {
         let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+        let cells: Vec<String> =
+            std::iter::once(format!("[Day {}]({})", timing.day.into_inner(), path))
+                .chain(
+                    columns
+                        .iter()
+                        .map(|column| format_cell(*column, &timing, total_nanos)),
+                )
+                .collect();
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
+    lines
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: &Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> String {
+    let header = format!("{prefix} Benchmarks");
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+    lines.extend(table_lines(timings, options));
+
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    if options.chart {
+        lines.push(String::new());
+        lines.push(format!("![Benchmark chart](./{CHART_PATH})"));
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+/// Renders a horizontal bar chart of the time of both parts per day as SVG.
+pub(crate) fn construct_chart(timings: &Timings, options: &TableOptions) -> String {
+    const WIDTH: f64 = 640.0;
+    const LABEL_WIDTH: f64 = 70.0;
+    const VALUE_WIDTH: f64 = 80.0;
+    const ROW_HEIGHT: f64 = 24.0;
+    const BAR_HEIGHT: f64 = 16.0;
+
+    let data = sorted_timings(timings, options);
+    let max_nanos = data
+        .iter()
+        .map(|t| t.total_nanos)
+        .fold(0.0, f64::max)
+        .max(f64::EPSILON);
+    let scale = (WIDTH - LABEL_WIDTH - VALUE_WIDTH) / max_nanos;
+
+    #[allow(clippy::cast_precision_loss)]
+    let height = ROW_HEIGHT * data.len() as f64 + ROW_HEIGHT;
+
+    let mut svg = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="sans-serif" font-size="12">"#
+        ),
+        format!(
+            r##"<text x="{LABEL_WIDTH}" y="14" fill="#e45c3a">■ Part 1</text><text x="{:.1}" y="14" fill="#3a7ee4">■ Part 2</text>"##,
+            LABEL_WIDTH + 70.0
+        ),
+    ];
+
+    for (i, timing) in data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = ROW_HEIGHT * (i + 1) as f64;
+        let part_1 = timing.part_1.unwrap_or(0.0) * scale;
+        let part_2 = timing.part_2.unwrap_or(0.0) * scale;
+
+        svg.push(format!(
+            r#"<text x="0" y="{:.1}">Day {}</text>"#,
+            y + BAR_HEIGHT - 4.0,
+            timing.day.into_inner()
+        ));
+        svg.push(format!(
+            r##"<rect x="{LABEL_WIDTH}" y="{y:.1}" width="{part_1:.1}" height="{BAR_HEIGHT}" fill="#e45c3a"/>"##
+        ));
+        svg.push(format!(
+            r##"<rect x="{:.1}" y="{y:.1}" width="{part_2:.1}" height="{BAR_HEIGHT}" fill="#3a7ee4"/>"##,
+            LABEL_WIDTH + part_1
+        ));
+        svg.push(format!(
+            r#"<text x="{:.1}" y="{:.1}">{}</text>"#,
+            LABEL_WIDTH + part_1 + part_2 + 6.0,
+            y + BAR_HEIGHT - 4.0,
+            format_nanos(timing.total_nanos)
+        ));
+    }
+
+    svg.push("</svg>".into());
+    svg.join("\n") + "\n"
+}
+
+/// Stars collected per day: the parts with an accepted answer in `data/answers.json`.
+/// Without an answers file, e.g. if answers are not submitted via `--submit`, the parts that have timings.
+fn count_stars(timings: &Timings, answers: Option<&Answers>) -> HashMap<Day, usize> {
+    all_days()
+        .map(|day| {
+            let stars = match answers {
+                Some(answers) => [1, 2]
+                    .into_iter()
+                    .filter(|part| answers.verified(day, *part).is_some())
+                    .count(),
+                None => timings.data.iter().find(|t| t.day == day).map_or(0, |t| {
+                    usize::from(t.part_1.is_some()) + usize::from(t.part_2.is_some())
+                }),
+            };
+            (day, stars)
+        })
+        .collect()
+}
+
+/// Renders the stars collected per day, see [`count_stars`].
+fn construct_progress(stars: &HashMap<Day, usize>) -> String {
+    let stars = |day: Day| stars.get(&day).copied().unwrap_or(0);
+
+    let days: Vec<Day> = all_days().collect();
+    let total: usize = days.iter().map(|day| stars(*day)).sum();
+
+    let mut lines: Vec<String> = vec![
+        PROGRESS_MARKER.into(),
+        "## Progress".into(),
+        String::new(),
+        format!("**⭐ {total} / {}**", days.len() * 2),
+        String::new(),
+    ];
+
+    lines.push(format!(
+        "| {} |",
+        days.iter()
+            .map(|day| day.into_inner().to_string())
+            .collect::<Vec<_>>()
+            .join(" | ")
+    ));
+    lines.push(format!("|{}", " :---: |".repeat(days.len())));
+    lines.push(format!(
+        "| {} |",
+        days.iter()
+            .map(|day| match stars(*day) {
+                0 => "-".to_string(),
+                n => "⭐".repeat(n),
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    ));
+
+    lines.push(PROGRESS_MARKER.into());
+    lines.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", &timings, total_millis, options);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
+/// Updates the progress section. It is opt-in, it is only updated if its markers are present.
+fn update_progress(s: &mut String, stars: &HashMap<Day, usize>) -> Result<(), Error> {
+    if s.contains(PROGRESS_MARKER) {
+        let positions = locate_section(s, PROGRESS_MARKER)?;
+        s.replace_range(
+            positions.pos_start..positions.pos_end,
+            &construct_progress(stars),
+        );
+    }
+
+    Ok(())
+}
+
 pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let options = TableOptions::from_env()?;
+    let answers = Answers::read_file_if_present()?;
+    let readme = fs::read(path).map_err(|e| Error::io("Failed to read README.md", e))?;
+    let mut readme = String::from_utf8_lossy(&readme).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+    update_content(&mut readme, timings.clone(), total_millis, &options)?;
+    update_progress(&mut readme, &count_stars(&timings, answers.as_ref()))?;
+    fs::write(path, &readme).map_err(|e| Error::io("Failed to update README.md", e))?;
+
+    if options.chart {
+        fs::create_dir_all(".assets")
+            .and_then(|()| fs::write(CHART_PATH, construct_chart(&timings, &options)))
+            .map_err(|e| Error::io("Failed to write benchmark chart", e))?;
+    }
+
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{
+        construct_chart, count_stars, update_content, update_progress, Column, SortKey,
+        TableOptions, MARKER, PROGRESS_MARKER,
+    };
+    use crate::{
+        day,
+        template::allocations::AllocStats,
+        template::answers::{Answers, SubmissionResult},
+        template::timings::Timing,
+        template::timings::Timings,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(10_000_000_f64),
+                    part_2: Some(20_000_000_f64),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(30_000_000_f64),
+                    part_2: Some(40_000_000_f64),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    part_1: Some(40_000_000_f64),
+                    part_2: Some(50_000_000_f64),
                     total_nanos: 9e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
@@ -131,28 +505,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +534,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,16 +542,142 @@ mod tests {
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_allocations() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_alloc = Some(AllocStats {
+            blocks: 3,
+            bytes: 2048,
+            peak_bytes: 1024,
+        });
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableOptions::default()).unwrap();
+        assert_eq!(
+            s.contains("| Day | Part 1 | Part 2 | Allocations (Part 1 / Part 2) |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` | `3 · 2.0 KiB` / `-` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` | `-` / `-` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_benchmarks_with_options() {
+        let mut timings = get_mock_timings();
+        timings.data[1].part_1_samples = Some(500);
+        let options = TableOptions {
+            columns: vec![Column::Total, Column::Samples, Column::Share, Column::Parse],
+            sort_by: SortKey::Total,
+            descending: true,
+            chart: true,
+        };
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &options).unwrap();
+        let expected = [
+            "foo",
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Total | Samples (Part 1 / Part 2) | Share |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 4](./src/bin/04.rs) | `90.0s` | `-` / `-` | 47.4% |",
+            "| [Day 2](./src/bin/02.rs) | `70.0s` | `500` / `-` | 36.8% |",
+            "| [Day 1](./src/bin/01.rs) | `30.0s` | `-` / `-` | 15.8% |",
             "",
             "**Total: 190.00ms**",
+            "",
+            "![Benchmark chart](./.assets/benchmarks.svg)",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_parse_times() {
+        let mut timings = get_mock_timings();
+        timings.data[0].parse = Some(5_000_000_f64);
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableOptions::default()).unwrap();
+        assert_eq!(s.contains("| Day | Parse | Part 1 | Part 2 |"), true);
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `5.0ms` | `10.0ms` | `20.0ms` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `-` | `30.0ms` | `40.0ms` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn updates_progress_section_if_present() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        let stars = count_stars(&timings, None);
+        let mut s = format!("{}\nold\n{}", PROGRESS_MARKER, PROGRESS_MARKER);
+        update_progress(&mut s, &stars).unwrap();
+        update_progress(&mut s, &stars).unwrap();
+        assert_eq!(s.matches(PROGRESS_MARKER).count(), 2);
+        assert_eq!(s.contains("old"), false);
+        assert_eq!(s.contains("**⭐ 5 / 50**"), true);
+        assert_eq!(s.contains("| ⭐⭐ | ⭐⭐ | - | ⭐ | - |"), true);
+    }
+
+    #[test]
+    fn counts_stars_from_accepted_answers() {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "11", SubmissionResult::Correct);
+        answers.record(day!(1), 2, "31", SubmissionResult::Correct);
+        answers.record(day!(2), 1, "2", SubmissionResult::Incorrect);
+        answers.record(day!(4), 1, "18", SubmissionResult::Correct);
+
+        // days 2 and 4 have timings of both parts, but no accepted answers for them.
+        let stars = count_stars(&get_mock_timings(), Some(&answers));
+        let mut s = format!("{}{}", PROGRESS_MARKER, PROGRESS_MARKER);
+        update_progress(&mut s, &stars).unwrap();
+        assert_eq!(s.contains("**⭐ 3 / 50**"), true);
+        assert_eq!(s.contains("| ⭐⭐ | - | - | ⭐ | - |"), true);
+    }
+
+    #[test]
+    fn skips_progress_section_if_absent() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, &TableOptions::default()).unwrap();
+        update_progress(&mut s, &count_stars(&get_mock_timings(), None)).unwrap();
+        assert_eq!(s.contains("Progress"), false);
+    }
+
+    #[test]
+    fn renders_chart() {
+        let svg = construct_chart(&get_mock_timings(), &TableOptions::default());
+        assert_eq!(svg.starts_with("<svg"), true);
+        assert_eq!(svg.matches("<rect").count(), 6);
+        assert_eq!(svg.contains("Day 4"), true);
+    }
+
+    #[test]
+    fn parses_columns() {
+        assert_eq!("total".parse::<Column>(), Ok(Column::Total));
+        assert_eq!("part_3".parse::<Column>().is_err(), true);
+    }
 }
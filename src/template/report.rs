@@ -0,0 +1,488 @@
+//! Benchmark reports for publishing outside of the readme.
+//! `cargo report` renders the stored timings, and the benchmark history if present, as CSV, Markdown or HTML.
+
+use std::{env, path::Path, str::FromStr};
+
+use crate::template::history::{
+    format_part, format_timestamp, format_trend, part_values, HistoryEntry,
+};
+use crate::template::readme_benchmarks::{
+    cell_values, construct_chart, sorted_timings, visible_columns, Column, TableOptions,
+};
+use crate::template::timings::{Timing, Timings};
+use crate::template::Day;
+
+/// Output format of a report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Markdown,
+    Html,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Format::Csv),
+            "md" | "markdown" => Ok(Format::Markdown),
+            "html" | "htm" => Ok(Format::Html),
+            other => Err(format!(
+                "unknown report format `{other}`, expected `csv`, `markdown` or `html`."
+            )),
+        }
+    }
+}
+
+impl Format {
+    /// Infers the format from the extension of an output path, e.g. `report.html`.
+    /// Returns `None` for paths without an extension and an error for unknown extensions.
+    pub fn from_path(path: &str) -> Result<Option<Self>, String> {
+        let Some(extension) = Path::new(path).extension() else {
+            return Ok(None);
+        };
+        let extension = extension.to_string_lossy();
+
+        match extension.to_lowercase().as_str() {
+            "csv" => Ok(Some(Format::Csv)),
+            "md" | "markdown" => Ok(Some(Format::Markdown)),
+            "html" | "htm" => Ok(Some(Format::Html)),
+            _ => Err(format!(
+                "unknown report extension `.{extension}`, expected `.csv`, `.md`, `.markdown`, `.html` or `.htm`. Pass `--format` to write it anyway."
+            )),
+        }
+    }
+}
+
+/// Renders a report of the timings, including the history of every day that has one.
+pub fn render(format: Format, timings: &Timings, history: &[HistoryEntry]) -> String {
+    match format {
+        Format::Csv => render_csv(timings),
+        Format::Markdown => render_markdown(timings, history),
+        Format::Html => render_html(timings, history),
+    }
+}
+
+fn title() -> String {
+    match env::var("AOC_YEAR") {
+        Ok(year) => format!("Advent of Code {year} Benchmarks"),
+        Err(_) => "Advent of Code Benchmarks".into(),
+    }
+}
+
+/// Every column, the ones without data are omitted.
+fn table_options() -> TableOptions {
+    TableOptions {
+        columns: vec![
+            Column::Parse,
+            Column::Part1,
+            Column::Part2,
+            Column::Total,
+            Column::Allocations,
+            Column::Samples,
+            Column::Share,
+        ],
+        ..TableOptions::default()
+    }
+}
+
+/// Header and one row of display values per day of the benchmark table.
+fn table(timings: &Timings) -> (Vec<&'static str>, Vec<Vec<String>>) {
+    let options = table_options();
+    let columns = visible_columns(timings, &options);
+    let total_nanos: f64 = timings.data.iter().map(|t| t.total_nanos).sum();
+
+    let titles = std::iter::once("Day")
+        .chain(columns.iter().map(|c| c.title()))
+        .collect();
+
+    let rows = sorted_timings(timings, &options)
+        .iter()
+        .map(|timing| {
+            std::iter::once(format!("Day {}", timing.day.into_inner()))
+                .chain(
+                    columns
+                        .iter()
+                        .map(|column| cell_values(*column, timing, total_nanos).join(" / ")),
+                )
+                .collect()
+        })
+        .collect();
+
+    (titles, rows)
+}
+
+/// Days with history entries, in order, with their entries.
+fn history_by_day(history: &[HistoryEntry]) -> Vec<(Day, Vec<&HistoryEntry>)> {
+    let mut days: Vec<Day> = history.iter().map(|e| e.day).collect();
+    days.sort_unstable();
+    days.dedup();
+
+    days.into_iter()
+        .map(|day| (day, history.iter().filter(|e| e.day == day).collect()))
+        .collect()
+}
+
+fn history_row(entry: &HistoryEntry) -> [String; 5] {
+    [
+        format_timestamp(entry.timestamp),
+        entry.commit.clone().unwrap_or_else(|| "-".into()),
+        entry.machine.clone(),
+        format_part(entry.part_1.as_ref()),
+        format_part(entry.part_2.as_ref()),
+    ]
+}
+
+const HISTORY_HEADER: [&str; 5] = ["Date (UTC)", "Commit", "Machine", "Part 1", "Part 2"];
+
+/* -------------------------------------------------------------------------- */
+
+fn render_csv(timings: &Timings) -> String {
+    let mut lines = vec![[
+        "day",
+        "parse_nanos",
+        "part_1_nanos",
+        "part_2_nanos",
+        "total_nanos",
+        "part_1_samples",
+        "part_2_samples",
+        "part_1_allocs",
+        "part_1_bytes",
+        "part_1_peak_bytes",
+        "part_2_allocs",
+        "part_2_bytes",
+        "part_2_peak_bytes",
+    ]
+    .join(",")];
+
+    let mut data = timings.data.clone();
+    data.sort_unstable_by_key(|t| t.day);
+
+    for timing in &data {
+        lines.push(csv_row(timing).join(","));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn csv_row(timing: &Timing) -> Vec<String> {
+    fn value<T: ToString>(x: Option<T>) -> String {
+        x.map(|x| x.to_string()).unwrap_or_default()
+    }
+
+    vec![
+        timing.day.to_string(),
+        value(timing.parse),
+        value(timing.part_1),
+        value(timing.part_2),
+        timing.total_nanos.to_string(),
+        value(timing.part_1_samples),
+        value(timing.part_2_samples),
+        value(timing.part_1_alloc.map(|a| a.blocks)),
+        value(timing.part_1_alloc.map(|a| a.bytes)),
+        value(timing.part_1_alloc.map(|a| a.peak_bytes)),
+        value(timing.part_2_alloc.map(|a| a.blocks)),
+        value(timing.part_2_alloc.map(|a| a.bytes)),
+        value(timing.part_2_alloc.map(|a| a.peak_bytes)),
+    ]
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn markdown_table<S: AsRef<str>>(titles: &[&str], rows: &[Vec<S>]) -> Vec<String> {
+    let mut lines = vec![
+        format!("| {} |", titles.join(" | ")),
+        format!("|{}", " :---: |".repeat(titles.len())),
+    ];
+
+    for row in rows {
+        let cells: Vec<&str> = row.iter().map(AsRef::as_ref).collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+
+    lines
+}
+
+fn render_markdown(timings: &Timings, history: &[HistoryEntry]) -> String {
+    let (titles, rows) = table(timings);
+
+    let mut lines = vec![format!("# {}", title()), String::new()];
+    lines.extend(markdown_table(&titles, &rows));
+    lines.push(String::new());
+    lines.push(format!("**Total: {:.2}ms**", timings.total_millis()));
+
+    let history = history_by_day(history);
+    if !history.is_empty() {
+        lines.push(String::new());
+        lines.push("## History".into());
+    }
+
+    for (day, entries) in history {
+        lines.push(String::new());
+        lines.push(format!("### Day {}", day.into_inner()));
+        lines.push(String::new());
+
+        let rows: Vec<Vec<String>> = entries.iter().map(|e| history_row(e).to_vec()).collect();
+        lines.extend(markdown_table(&HISTORY_HEADER, &rows));
+
+        let trends: Vec<String> = [
+            ("Part 1", part_values(&entries, |e| e.part_1.as_ref())),
+            ("Part 2", part_values(&entries, |e| e.part_2.as_ref())),
+        ]
+        .into_iter()
+        .filter_map(|(part, values)| format_trend(&values).map(|t| format!("{part}: {t}")))
+        .collect();
+
+        if !trends.is_empty() {
+            lines.push(String::new());
+            lines.push(trends.join("  \n"));
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+const HTML_STYLE: &str = "body { font-family: sans-serif; max-width: 60rem; margin: 2rem auto; padding: 0 1rem; color: #222; }
+table { border-collapse: collapse; margin: 1rem 0; }
+th, td { border: 1px solid #ccc; padding: 0.3rem 0.8rem; text-align: center; }
+th { background: #f3f3f3; }
+figure { margin: 1rem 0; }";
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html_table<S: AsRef<str>>(titles: &[&str], rows: &[Vec<S>]) -> String {
+    let mut html = vec!["<table>".to_string(), "<tr>".into()];
+    html.extend(
+        titles
+            .iter()
+            .map(|t| format!("<th>{}</th>", escape_html(t))),
+    );
+    html.push("</tr>".into());
+
+    for row in rows {
+        html.push("<tr>".into());
+        html.extend(
+            row.iter()
+                .map(|cell| format!("<td>{}</td>", escape_html(cell.as_ref()))),
+        );
+        html.push("</tr>".into());
+    }
+
+    html.push("</table>".into());
+    html.join("")
+}
+
+/// Renders the times of both parts across history entries as an SVG line chart.
+fn history_chart(part_1: &[f64], part_2: &[f64]) -> String {
+    const WIDTH: f64 = 480.0;
+    const HEIGHT: f64 = 120.0;
+    const PADDING: f64 = 8.0;
+
+    let max = part_1
+        .iter()
+        .chain(part_2)
+        .copied()
+        .fold(0.0, f64::max)
+        .max(f64::EPSILON);
+
+    let polyline = |values: &[f64], color: &str| {
+        #[allow(clippy::cast_precision_loss)]
+        let step = (WIDTH - 2.0 * PADDING) / (values.len().max(2) - 1) as f64;
+        let points: Vec<String> = values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                #[allow(clippy::cast_precision_loss)]
+                let x = PADDING + step * i as f64;
+                let y = HEIGHT - PADDING - v / max * (HEIGHT - 2.0 * PADDING);
+                format!("{x:.1},{y:.1}")
+            })
+            .collect();
+        format!(
+            r#"<polyline points="{}" fill="none" stroke="{color}" stroke-width="2"/>"#,
+            points.join(" ")
+        )
+    };
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" viewBox="0 0 {WIDTH} {HEIGHT}">{}{}</svg>"##,
+        polyline(part_1, "#e45c3a"),
+        polyline(part_2, "#3a7ee4")
+    )
+}
+
+fn render_html(timings: &Timings, history: &[HistoryEntry]) -> String {
+    let title = escape_html(&title());
+    let (titles, rows) = table(timings);
+
+    let mut html = vec![
+        "<!DOCTYPE html>".to_string(),
+        r#"<html lang="en">"#.into(),
+        r#"<head><meta charset="utf-8">"#.into(),
+        format!("<title>{title}</title>"),
+        format!("<style>\n{HTML_STYLE}\n</style>"),
+        "</head>".into(),
+        "<body>".into(),
+        format!("<h1>{title}</h1>"),
+        html_table(&titles, &rows),
+        format!(
+            "<p><strong>Total: {:.2}ms</strong></p>",
+            timings.total_millis()
+        ),
+    ];
+
+    if !timings.data.is_empty() {
+        html.push(format!(
+            "<figure>{}</figure>",
+            construct_chart(timings, &table_options()).trim_end()
+        ));
+    }
+
+    let history = history_by_day(history);
+    if !history.is_empty() {
+        html.push("<h2>History</h2>".into());
+    }
+
+    for (day, entries) in history {
+        html.push(format!("<h3>Day {}</h3>", day.into_inner()));
+
+        let rows: Vec<Vec<String>> = entries.iter().map(|e| history_row(e).to_vec()).collect();
+        html.push(html_table(&HISTORY_HEADER, &rows));
+
+        let part_1 = part_values(&entries, |e| e.part_1.as_ref());
+        let part_2 = part_values(&entries, |e| e.part_2.as_ref());
+        if part_1.len() > 1 || part_2.len() > 1 {
+            html.push(format!(
+                "<figure>{}</figure>",
+                history_chart(&part_1, &part_2)
+            ));
+        }
+    }
+
+    html.push("</body>".into());
+    html.push("</html>".into());
+    html.join("\n") + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{escape_html, render, Format};
+    use crate::{
+        day,
+        template::history::{HistoryEntry, PartRecord},
+        template::timings::{Timing, Timings},
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    part_1: Some(30_000_000_f64),
+                    part_1_samples: Some(100),
+                    total_nanos: 30_000_000_f64,
+                    ..Timing::new(day!(2))
+                },
+                Timing {
+                    part_1: Some(10_000_000_f64),
+                    part_2: Some(20_000_000_f64),
+                    total_nanos: 30_000_000_f64,
+                    ..Timing::new(day!(1))
+                },
+            ],
+        }
+    }
+
+    fn get_mock_history() -> Vec<HistoryEntry> {
+        [20_000_000_f64, 10_000_000_f64]
+            .iter()
+            .enumerate()
+            .map(|(i, nanos)| HistoryEntry {
+                timestamp: 1733720000 + i as u64 * 86_400,
+                commit: Some(format!("abc123{i}")),
+                machine: "<box>".into(),
+                day: day!(1),
+                part_1: Some(PartRecord {
+                    nanos: *nanos,
+                    alloc: None,
+                    samples: None,
+                }),
+                part_2: None,
+                total_nanos: *nanos,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("HTML".parse::<Format>(), Ok(Format::Html));
+        assert_eq!(
+            Format::from_path("out/report.md"),
+            Ok(Some(Format::Markdown))
+        );
+        assert_eq!(Format::from_path("report.HTM"), Ok(Some(Format::Html)));
+        assert_eq!(Format::from_path("report.csv"), Ok(Some(Format::Csv)));
+        assert_eq!(Format::from_path("out.d/report"), Ok(None));
+        assert!(Format::from_path("report.txt").is_err());
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = render(Format::Csv, &get_mock_timings(), &[]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].starts_with("day,parse_nanos,part_1_nanos"), true);
+        assert_eq!(lines[1], "01,,10000000,20000000,30000000,,,,,,,,");
+        assert_eq!(lines[2], "02,,30000000,,30000000,100,,,,,,,");
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let md = render(Format::Markdown, &get_mock_timings(), &get_mock_history());
+        assert_eq!(
+            md.contains("| Day | Part 1 | Part 2 | Total | Samples (Part 1 / Part 2) | Share |"),
+            true
+        );
+        assert_eq!(
+            md.contains("| Day 1 | 10.0ms | 20.0ms | 30.0ms | - / - | 50.0% |"),
+            true
+        );
+        assert_eq!(md.contains("**Total: 60.00ms**"), true);
+        assert_eq!(md.contains("### Day 1"), true);
+        assert_eq!(
+            md.contains("| 2024-12-10 04:53 | abc1231 | <box> | 10.0ms | - |"),
+            true
+        );
+        assert_eq!(md.contains("Part 1: █▁ 20.0ms → 10.0ms (-50.0%)"), true);
+    }
+
+    #[test]
+    fn renders_html() {
+        let html = render(Format::Html, &get_mock_timings(), &get_mock_history());
+        assert_eq!(html.starts_with("<!DOCTYPE html>"), true);
+        assert_eq!(
+            html.contains("<td>Day 2</td><td>30.0ms</td><td>-</td>"),
+            true
+        );
+        assert_eq!(html.contains("&lt;box&gt;"), true);
+        assert_eq!(html.matches("<svg").count(), 2);
+        assert_eq!(html.trim_end().ends_with("</html>"), true);
+    }
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(
+            escape_html(r#"<a href="x">&</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+    }
+}
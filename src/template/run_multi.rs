@@ -1,38 +1,42 @@
-use std::{collections::HashSet, io};
+use std::collections::HashSet;
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::error::Error;
+use crate::template::{allocations::AllocTracking, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    alloc_tracking: AllocTracking,
+) -> Result<Option<Timings>, Error> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    for day in all_days().filter(|day| days_to_run.contains(day)) {
+        if need_space {
+            println!();
+        }
+        need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output = child_commands::run_solution(day, is_timed, is_release, alloc_tracking)?;
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+        if output.is_empty() {
+            println!("Not solved.");
+        } else {
+            let val = child_commands::parse_exec_time(&output, day);
+            timings.push(val);
+        }
+    }
 
     if is_timed {
         let timings = Timings { data: timings };
@@ -40,22 +44,9 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
+        Ok(Some(timings))
     } else {
-        None
-    }
-}
-
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
-}
-
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+        Ok(None)
     }
 }
 
@@ -67,17 +58,26 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::get_path_for_bin;
+    use crate::template::error::Error;
+    use crate::template::{
+        allocations::{AllocStats, AllocTracking},
+        Day,
+    };
     use std::{
-        io::{BufRead, BufReader},
+        io::{self, BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
         thread,
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        alloc_tracking: AllocTracking,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -86,8 +86,19 @@ pub mod child_commands {
         let day_padded = day.to_string();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
-        if is_release {
-            args.push("--release");
+        match alloc_tracking {
+            AllocTracking::Dhat => args.extend(["--profile", "dhat", "--features", "dhat-heap"]),
+            AllocTracking::Counting => {
+                args.extend(["--features", "alloc-stats"]);
+                if is_release {
+                    args.push("--release");
+                }
+            }
+            AllocTracking::Off => {
+                if is_release {
+                    args.push("--release");
+                }
+            }
         }
 
         if is_timed {
@@ -103,10 +114,12 @@ pub mod child_commands {
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .spawn()?;
+            .spawn()
+            .map_err(|e| Error::io("Failed to run solution", e))?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let broken_pipe = || Error::io("Failed to run solution", io::ErrorKind::BrokenPipe.into());
+        let stdout = BufReader::new(cmd.stdout.take().ok_or_else(broken_pipe)?);
+        let stderr = BufReader::new(cmd.stderr.take().ok_or_else(broken_pipe)?);
 
         let mut output = vec![];
 
@@ -123,18 +136,14 @@ pub mod child_commands {
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        cmd.wait()
+            .map_err(|e| Error::io("Failed to run solution", e))?;
 
         Ok(output)
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+        let mut timings = super::Timing::new(day);
 
         output
             .iter()
@@ -143,19 +152,34 @@ pub mod child_commands {
                     return None;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
+                let Some(nanos) = parse_time(l) else {
                     eprintln!("Could not parse timings from line: {l}");
                     return None;
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((
+                    part,
+                    nanos,
+                    parse_samples(l),
+                    AllocStats::from_output_line(l),
+                ))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, nanos, samples, alloc_stats)| {
+                if part.contains("Parse") {
+                    // parsing is part of both parts already, so it does not count towards the total.
+                    timings.parse = Some(nanos);
+                    return;
+                }
+
                 if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
+                    timings.part_1 = Some(nanos);
+                    timings.part_1_alloc = alloc_stats;
+                    timings.part_1_samples = samples;
                 } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                    timings.part_2 = Some(nanos);
+                    timings.part_2_alloc = alloc_stats;
+                    timings.part_2_samples = samples;
                 }
 
                 timings.total_nanos += nanos;
@@ -168,25 +192,38 @@ pub mod child_commands {
         s.split(postfix).next()?.parse().ok()
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    fn parse_time(line: &str) -> Option<f64> {
         let str_timing = line
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
+        parse_duration(str_timing)
+    }
+
+    fn parse_samples(line: &str) -> Option<u64> {
+        line.split(" samples)")
+            .next()?
+            .split('@')
+            .next_back()?
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    /// Parses a duration formatted with `{:.1?}` (e.g. `74.13ms`) into nanoseconds.
+    pub fn parse_duration(str_timing: &str) -> Option<f64> {
+        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+        match str_timing {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
             s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
             s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
             s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        }
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -220,8 +257,8 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.part_1.unwrap(), 74.13_f64);
+            assert_approx_eq!(res.part_2.unwrap(), 74130000_f64);
         }
 
         #[test]
@@ -235,8 +272,42 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.part_1.unwrap(), 2000000000_f64);
+            assert_approx_eq!(res.part_2.unwrap(), 100000000_f64);
+        }
+
+        #[test]
+        fn parses_alloc_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [3 allocs, 276 B total, 232 B peak]"
+                        .into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
+            assert_approx_eq!(res.part_1.unwrap(), 74.13_f64);
+            assert_eq!(res.part_1_alloc.unwrap().blocks, 3);
+            assert_eq!(res.part_2_alloc.is_none(), true);
+        }
+
+        #[test]
+        fn parses_parse_time_and_samples() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: (12.0µs @ 5000 samples)".into(),
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
+            assert_approx_eq!(res.parse.unwrap(), 12000_f64);
+            assert_eq!(res.part_1_samples, Some(100000));
+            assert_eq!(res.part_2_samples, Some(99999));
         }
 
         #[test]
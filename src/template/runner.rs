@@ -1,45 +1,265 @@
-/// Encapsulates code that interacts with solution functions.
+//! Encapsulates code that interacts with solution functions.
+
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
+use std::path::Path;
 use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env};
 
+use crate::template::allocations::{self, AllocStats};
+use crate::template::answers::{parse_cooldown, Answers, SubmissionResult};
+use crate::template::clock;
+use crate::template::compare::Implementation;
+use crate::template::error::Error;
+use crate::template::fuzz::FuzzTarget;
+use crate::template::inputs;
 use crate::template::ANSI_BOLD;
 use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// A parse function registered with the `parse(...)` option of `solution!`, see [`run_parse`].
+pub type ParseFn = Box<dyn Fn(&str)>;
+
+/// Additional implementations and helpers a day registers via the options of `solution!`.
+#[derive(Default)]
+pub struct Registry {
+    pub fuzz: Vec<FuzzTarget>,
+    pub alternatives: Vec<Implementation>,
+    pub parse: Option<ParseFn>,
+}
+
+/// Time the registered parse function of a day on its own. The timings of the parts still include parsing.
+pub fn run_parse(parse: &dyn Fn(&str), input: &str) {
+    print!("Parse:");
+    let _ = stdout().flush();
+
+    let timer = Instant::now();
+    parse(input);
+    let base_time = timer.elapsed();
+
+    let (duration, samples) = if std::env::args().any(|x| x == "--time") {
+        bench(parse, input, &base_time)
+    } else {
+        (base_time, 1)
+    };
+
+    println!("\rParse:{}", format_duration(&duration, samples));
+}
+
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+) -> Result<(), Error> {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples, alloc_stats) = run_timed(func, input, day, part, |result| {
+        print_result(result, &part_str, "");
+    });
+
+    let mut duration_str = format_duration(&duration, samples);
+    if let Some(alloc_stats) = alloc_stats {
+        duration_str.push_str(&format!(" {alloc_stats}"));
+    }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, &part_str, &duration_str);
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        let answer = result.to_string();
+        if let Some(output) = submit_result(result, day, part)? {
+            let result = record_submission(day, part, &answer, &output);
+            if part == 1 && result == SubmissionResult::Correct {
+                refresh_puzzle(day);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// A part of a solution, with its result converted to a string.
+pub type PartFn<'a> = &'a dyn Fn(&str) -> Option<String>;
+
+/// Runs parts on the real input of a day and asserts that they return the answers accepted by the website.
+/// Parts without an accepted answer in `data/answers.json` are skipped. Used by the tests of `input_tests()`.
+#[track_caller]
+pub fn check_input_answers(day: Day, parts: &[(u8, PartFn)]) {
+    let input = read_input(day).unwrap_or_else(|e| panic!("{e}"));
+    let answers = Answers::read_from_file().unwrap_or_else(|e| panic!("{e}"));
+
+    for (part, func) in parts {
+        match answers.verified(day, *part) {
+            Some(expected) => assert_eq!(
+                func(&input).as_deref(),
+                Some(expected),
+                "part {part} of day {day} does not return its accepted answer"
+            ),
+            None => println!("Part {part}: no accepted answer recorded, skipped."),
+        }
+    }
+}
+
+/// Reads the input of a day and checks it before the parts are called, so a broken input file is reported
+/// before a part panics or returns a wrong answer. Windows line endings are normalized to `\n`.
+pub fn read_input(day: Day) -> Result<String, Error> {
+    let path = format!("data/inputs/{day}.txt");
+
+    let input = inputs::read(Path::new(&path)).map_err(|e| match e {
+        Error::NotFound(_) => Error::NotFound(format!(
+            "\"{path}\" does not exist. Download it with `cargo download {}`.",
+            day.into_inner()
+        )),
+        e => e,
+    })?;
+
+    if let Some(problem) = InputProblem::detect(&input) {
+        eprintln!("Warning: {}", problem.describe(day, &path));
+    }
+
+    Ok(normalize_input(input))
+}
+
+/// Contents of an input file that cannot be the puzzle input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputProblem {
+    /// E.g. the empty file created by `scaffold`.
+    Empty,
+    /// A web page saved instead of the input, most likely the login page of an expired session.
+    WebPage,
+    /// The response of the website to a request without a session.
+    LoggedOut,
+    /// The response of the website to a request before the puzzle unlocked.
+    NotUnlocked,
+    /// Downloaded inputs end with a newline, so the input may be cut off, e.g. when it was pasted.
+    MissingNewline,
+}
+
+impl InputProblem {
+    pub fn detect(input: &str) -> Option<Self> {
+        let first_line = input.trim_start().lines().next().unwrap_or_default();
+        let starts_with = |prefix: &str| {
+            first_line
+                .get(..prefix.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+        };
+
+        if input.trim().is_empty() {
+            Some(Self::Empty)
+        } else if starts_with("<!doctype html") || starts_with("<html") {
+            Some(Self::WebPage)
+        } else if first_line.contains("Please log in to get your puzzle input") {
+            Some(Self::LoggedOut)
+        } else if first_line
+            .contains("Please don't repeatedly request this endpoint before it unlocks")
+        {
+            Some(Self::NotUnlocked)
+        } else if !input.ends_with('\n') {
+            Some(Self::MissingNewline)
+        } else {
+            None
+        }
+    }
+
+    fn describe(self, day: Day, path: &str) -> String {
+        let download = format!("cargo download {}", day.into_inner());
+
+        match self {
+            Self::Empty => format!(
+                "the input of day {day} is empty. Download it with `{download}` or paste it into \"{path}\"."
+            ),
+            Self::WebPage => format!(
+                "\"{path}\" contains a web page instead of the input, most likely the login page of an expired session. \
+                Update your session cookie and run `{download}` again."
+            ),
+            Self::LoggedOut => format!(
+                "\"{path}\" contains an error message instead of the input, the session was not logged in. \
+                Update your session cookie and run `{download}` again."
+            ),
+            Self::NotUnlocked => format!(
+                "\"{path}\" contains an error message instead of the input, the puzzle was not unlocked yet. \
+                Run `{download}` again."
+            ),
+            Self::MissingNewline => format!(
+                "\"{path}\" does not end with a newline, it may be cut off. \
+                If the answers are wrong, run `{download}` to download it again."
+            ),
+        }
+    }
+}
+
+fn normalize_input(input: String) -> String {
+    if input.contains('\r') {
+        input.replace("\r\n", "\n")
+    } else {
+        input
+    }
+}
+
+/// Records the outcome of a submission in `data/answers.json`.
+fn record_submission(day: Day, part: u8, answer: &str, output: &Output) -> SubmissionResult {
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let result = SubmissionResult::from_output(&text);
+    let cooldown = parse_cooldown(&text).map(|seconds| clock::from_env().unix_seconds() + seconds);
+
+    // the stored answers are not overwritten if they cannot be read.
+    let mut answers = match Answers::read_from_file() {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Not recording the submitted answer: {e}");
+            return result;
+        }
+    };
+    let recorded = answers.record(day, part, answer, result);
+    if answers.set_cooldown(day, part, cooldown) || recorded {
+        if let Err(e) = answers.store_file() {
+            eprintln!("Failed to store submitted answer: {e}");
+        }
+    }
+
+    result
+}
+
+/// Downloads the puzzle again after part one was solved, so the description includes part two.
+fn refresh_puzzle(day: Day) {
+    println!("Downloading part two of the puzzle...");
+    match aoc_cli::download_puzzle(day) {
+        Ok(_) => println!("🎄 Type `cargo read {day} --part 2` to read part two."),
+        Err(e) => eprintln!("Failed to download part two: {e}"),
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// When a heap profiler is compiled in, allocations of the first execution are recorded.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    #[allow(unused_variables)] day: Day,
+    #[allow(unused_variables)] part: u8,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
+) -> (T, Duration, u128, Option<AllocStats>) {
+    let (result, base_time, alloc_stats) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+        let _profiler = allocations::start_dhat_profiler(day, part);
 
-        func(input)
+        allocations::reset_counters();
+        let timer = Instant::now();
+        let result = func(input);
+        let base_time = timer.elapsed();
+
+        (result, base_time, allocations::current_stats())
     };
-    let base_time = timer.elapsed();
 
     hook(&result);
 
@@ -49,10 +269,14 @@ fn run_timed<I: Clone, T>(
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, alloc_stats)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+pub(crate) fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (Duration, u128) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -132,38 +356,84 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///
+/// Returns the output of aoc-cli if the part was submitted. aoc-cli exits with a non-zero status
+/// for wrong answers, so its output is returned for those as well.
+fn submit_result<T: Display>(result: T, day: Day, part: u8) -> Result<Option<Output>, Error> {
     let args: Vec<String> = env::args().collect();
 
-    if !args.contains(&"--submit".into()) {
-        return None;
-    }
-
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    }
-
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    let Some(submit_index) = args.iter().position(|x| x == "--submit") else {
+        return Ok(None);
+    };
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let Some(part_submit) = args
+        .get(submit_index + 1)
+        .and_then(|x| x.parse::<u8>().ok())
+    else {
+        return Err(Error::Usage(
+            "Unexpected command-line input. Format: cargo solve 1 --submit 1".into(),
+        ));
     };
 
     if part_submit != part {
-        return None;
+        return Ok(None);
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if let Some(remaining) =
+        Answers::read_from_file()?.cooldown_remaining(day, part, clock::from_env().as_ref())
+    {
+        eprintln!(
+            "Day {day} part {part} is on cooldown after a wrong answer, try again in {remaining}s."
+        );
+        return Ok(None);
     }
 
+    aoc_cli::check()?;
+
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    match aoc_cli::submit(day, part, &result.to_string()) {
+        Ok(output) | Err(aoc_cli::AocCommandError::BadExitStatus(output)) => Ok(Some(output)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{normalize_input, InputProblem};
+
+    #[test]
+    fn normalizes_inputs() {
+        assert_eq!(normalize_input("1 2\r\n3 4\r\n".into()), "1 2\n3 4\n");
+        assert_eq!(normalize_input("1 2\n3 4".into()), "1 2\n3 4");
+    }
+
+    #[test]
+    fn detects_broken_inputs() {
+        assert_eq!(InputProblem::detect("3   4\n4   3\n"), None);
+        // only the first line is checked.
+        assert_eq!(InputProblem::detect("1 2\n<html>\n"), None);
+        assert_eq!(InputProblem::detect(""), Some(InputProblem::Empty));
+        assert_eq!(InputProblem::detect("\n \n"), Some(InputProblem::Empty));
+        assert_eq!(
+            InputProblem::detect("\n<!DOCTYPE html>\n<html lang=\"en-us\">\n"),
+            Some(InputProblem::WebPage)
+        );
+        assert_eq!(
+            InputProblem::detect(
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+            ),
+            Some(InputProblem::LoggedOut)
+        );
+        assert_eq!(
+            InputProblem::detect("Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n"),
+            Some(InputProblem::NotUnlocked)
+        );
+        assert_eq!(
+            InputProblem::detect("3   4\n4   3"),
+            Some(InputProblem::MissingNewline)
+        );
+        assert_eq!(InputProblem::detect("3   4\r\n4   3\r\n"), None);
+    }
 }
@@ -0,0 +1,313 @@
+//! Progress overview of all days.
+//! Combines the solution files, inputs, submitted answers and stored benchmarks into one status per day.
+
+use std::{collections::HashMap, fs, path::Path};
+use tinyjson::JsonValue;
+
+use crate::template::answers::Answers;
+use crate::template::inputs;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::table::format_table;
+use crate::template::timings::{format_nanos, Timings};
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_RESET};
+
+/// How far a day has progressed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Progress {
+    Unstarted,
+    /// A solution file exists, but no answer was accepted yet.
+    Scaffolded,
+    PartOne,
+    BothStars,
+}
+
+impl Progress {
+    fn key(self) -> &'static str {
+        match self {
+            Progress::Unstarted => "unstarted",
+            Progress::Scaffolded => "scaffolded",
+            Progress::PartOne => "part_one",
+            Progress::BothStars => "both_stars",
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Progress::Unstarted => "  ",
+            Progress::Scaffolded => "☆☆",
+            Progress::PartOne => "★☆",
+            Progress::BothStars => "★★",
+        }
+    }
+}
+
+/// Status of one part of a day.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartStatus {
+    /// The answer accepted by the website.
+    pub answer: Option<String>,
+    /// Number of answers rejected by the website.
+    pub rejected: usize,
+    /// Stored benchmark time in nanoseconds.
+    pub nanos: Option<f64>,
+}
+
+impl PartStatus {
+    fn summary(&self) -> String {
+        match (&self.answer, self.rejected, self.nanos) {
+            (Some(_), _, _) => "✓".into(),
+            (None, 0, Some(_)) => "?".into(),
+            (None, 0, None) => "-".into(),
+            (None, rejected, _) => format!("✖ {rejected}"),
+        }
+    }
+}
+
+/// Status of one day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayStatus {
+    pub day: Day,
+    pub scaffolded: bool,
+    pub has_input: bool,
+    pub part_1: PartStatus,
+    pub part_2: PartStatus,
+}
+
+impl DayStatus {
+    pub fn new(
+        day: Day,
+        scaffolded: bool,
+        has_input: bool,
+        answers: &Answers,
+        timings: &Timings,
+    ) -> Self {
+        let timing = timings.data.iter().find(|t| t.day == day);
+        let part = |part: u8| {
+            let answers = answers.day(day).map(|a| a.part(part));
+            PartStatus {
+                answer: answers.and_then(|a| a.answer.clone()),
+                rejected: answers.map_or(0, |a| a.rejected.len()),
+                nanos: timing.and_then(|t| if part == 1 { t.part_1 } else { t.part_2 }),
+            }
+        };
+
+        Self {
+            day,
+            scaffolded,
+            has_input,
+            part_1: part(1),
+            part_2: part(2),
+        }
+    }
+
+    pub fn progress(&self) -> Progress {
+        match (self.part_1.answer.is_some(), self.part_2.answer.is_some()) {
+            (true, true) => Progress::BothStars,
+            (true, false) | (false, true) => Progress::PartOne,
+            _ if self.scaffolded => Progress::Scaffolded,
+            _ => Progress::Unstarted,
+        }
+    }
+
+    pub fn stars(&self) -> usize {
+        usize::from(self.part_1.answer.is_some()) + usize::from(self.part_2.answer.is_some())
+    }
+}
+
+/// Collects the status of every day from the files in the project.
+pub fn collect() -> Vec<DayStatus> {
+    // the status is shown without answers or times if they cannot be read.
+    let answers = Answers::read_from_file().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        Answers::default()
+    });
+    let timings = Timings::read_from_file().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        Timings::default()
+    });
+
+    all_days()
+        .map(|day| {
+            let scaffolded = fs::metadata(get_path_for_bin(day)).is_ok();
+            let has_input = inputs::is_readable(Path::new(&format!("data/inputs/{day}.txt")));
+            DayStatus::new(day, scaffolded, has_input, &answers, &timings)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders the days as a calendar of five weeks, followed by details of every started day.
+pub fn format_calendar(statuses: &[DayStatus]) -> String {
+    let stars: usize = statuses.iter().map(DayStatus::stars).sum();
+
+    let mut lines = vec![format!(
+        "{ANSI_BOLD}⭐ {stars} / {}{ANSI_RESET}",
+        statuses.len() * 2
+    )];
+    lines.push(String::new());
+
+    for week in statuses.chunks(5) {
+        lines.push(
+            week.iter()
+                .map(|s| format!("{} {}", s.day, s.progress().symbol()))
+                .collect::<Vec<_>>()
+                .join("   ")
+                .trim_end()
+                .to_string(),
+        );
+    }
+
+    let started: Vec<&DayStatus> = statuses
+        .iter()
+        .filter(|s| s.progress() != Progress::Unstarted)
+        .collect();
+
+    if started.is_empty() {
+        return lines.join("\n");
+    }
+
+    lines.push(String::new());
+
+    let header = ["Day", "Status", "Input", "Part 1", "Part 2", "Time"];
+    let rows: Vec<[String; 6]> = started
+        .iter()
+        .map(|s| {
+            let time = match (s.part_1.nanos, s.part_2.nanos) {
+                (None, None) => "-".into(),
+                (a, b) => format_nanos(a.unwrap_or(0.0) + b.unwrap_or(0.0)),
+            };
+
+            [
+                s.day.to_string(),
+                s.progress().key().replace('_', " "),
+                if s.has_input { "✓" } else { "-" }.into(),
+                s.part_1.summary(),
+                s.part_2.summary(),
+                time,
+            ]
+        })
+        .collect();
+
+    lines.push(format_table(&header, &rows));
+
+    lines.push(String::new());
+    lines.push("✓ accepted answer, ? solved but not submitted, ✖ rejected answers".into());
+
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartStatus> for JsonValue {
+    fn from(value: &PartStatus) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("rejected".into(), JsonValue::Number(value.rejected as f64));
+        map.insert(
+            "nanos".into(),
+            value.nanos.map_or(JsonValue::Null, JsonValue::Number),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&DayStatus> for JsonValue {
+    fn from(value: &DayStatus) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "progress".into(),
+            JsonValue::String(value.progress().key().into()),
+        );
+        map.insert("scaffolded".into(), JsonValue::Boolean(value.scaffolded));
+        map.insert("input".into(), JsonValue::Boolean(value.has_input));
+        map.insert("part_1".into(), JsonValue::from(&value.part_1));
+        map.insert("part_2".into(), JsonValue::from(&value.part_2));
+
+        JsonValue::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_calendar, DayStatus, Progress};
+    use crate::{
+        day,
+        template::answers::{Answers, SubmissionResult},
+        template::timings::{Timing, Timings},
+    };
+
+    fn get_mock_data() -> (Answers, Timings) {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "11", SubmissionResult::Correct);
+        answers.record(day!(1), 2, "31", SubmissionResult::Correct);
+        answers.record(day!(2), 1, "2", SubmissionResult::Correct);
+        answers.record(day!(2), 2, "3", SubmissionResult::Incorrect);
+
+        let timings = Timings {
+            data: vec![Timing {
+                part_1: Some(1_000_000_f64),
+                total_nanos: 1_000_000_f64,
+                ..Timing::new(day!(3))
+            }],
+        };
+
+        (answers, timings)
+    }
+
+    fn get_mock_statuses() -> Vec<DayStatus> {
+        let (answers, timings) = get_mock_data();
+        (1..=5)
+            .map(|day| {
+                let day = crate::template::Day::new(day).unwrap();
+                DayStatus::new(day, day <= 3, day <= 2, &answers, &timings)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn derives_progress() {
+        let statuses = get_mock_statuses();
+        assert_eq!(statuses[0].progress(), Progress::BothStars);
+        assert_eq!(statuses[1].progress(), Progress::PartOne);
+        assert_eq!(statuses[1].part_2.rejected, 1);
+        assert_eq!(statuses[2].progress(), Progress::Scaffolded);
+        assert_eq!(statuses[2].part_1.nanos, Some(1_000_000_f64));
+        assert_eq!(statuses[3].progress(), Progress::Unstarted);
+    }
+
+    #[test]
+    fn formats_calendar() {
+        let calendar = format_calendar(&get_mock_statuses());
+        let lines: Vec<&str> = calendar.lines().collect();
+        assert_eq!(lines[0].contains("⭐ 3 / 10"), true);
+        assert_eq!(lines[2], "01 ★★   02 ★☆   03 ☆☆   04      05");
+        assert_eq!(lines[5], "01   both stars  ✓      ✓       ✓       -");
+        assert_eq!(lines[6], "02   part one    ✓      ✓       ✖ 1     -");
+        assert_eq!(lines[7], "03   scaffolded  -      ?       -       1.0ms");
+    }
+
+    #[test]
+    fn serializes_status() {
+        let statuses = get_mock_statuses();
+        let json = JsonValue::from(&statuses[1]).stringify().unwrap();
+        assert_eq!(json.contains(r#""progress":"part_one""#), true);
+        assert_eq!(json.contains(r#""answer":"2""#), true);
+    }
+}
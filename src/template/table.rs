@@ -0,0 +1,59 @@
+//! Tables printed by the commands, e.g. `cargo status` or `cargo bench-compare`.
+
+use crate::template::{ANSI_ITALIC, ANSI_RESET};
+
+/// Returns the lines of a table with an italic header. Cells are padded to the widest cell of their column.
+pub fn format_table<R: AsRef<[String]>>(header: &[&str], rows: &[R]) -> String {
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row.as_ref()[i].chars().count())
+                .chain(std::iter::once(header[i].chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let format_row = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    std::iter::once(format!(
+        "{ANSI_ITALIC}{}{ANSI_RESET}",
+        format_row(header.to_vec())
+    ))
+    .chain(
+        rows.iter()
+            .map(|row| format_row(row.as_ref().iter().map(String::as_str).collect())),
+    )
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_table;
+    use crate::template::{ANSI_ITALIC, ANSI_RESET};
+
+    #[test]
+    fn pads_columns() {
+        let rows = [
+            ["1".to_string(), "✓".to_string(), "74.1ms".to_string()],
+            ["12".to_string(), "-".to_string(), String::new()],
+        ];
+
+        assert_eq!(
+            format_table(&["Day", "Input", "Time"], &rows),
+            format!("{ANSI_ITALIC}Day  Input  Time{ANSI_RESET}\n1    ✓      74.1ms\n12   -")
+        );
+    }
+}
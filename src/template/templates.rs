@@ -0,0 +1,276 @@
+//! Solution templates used by `cargo scaffold`.
+//! Built-in templates are embedded into the binary; templates in the `templates/` directory of the
+//! project are loaded at runtime and take precedence over built-in templates of the same name.
+
+use std::{env, fs, path::Path};
+
+use crate::template::examples;
+use crate::template::puzzle;
+use crate::template::Day;
+
+/// Directory with user templates, relative to the project root. Each `<name>.txt` file is a template.
+pub const USER_TEMPLATE_DIR: &str = "templates";
+
+pub const DEFAULT_TEMPLATE: &str = "plain";
+
+const BUILTIN_TEMPLATES: [(&str, &str); 4] = [
+    (
+        "plain",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/plain.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+    ),
+    (
+        "parse-once",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/parse-once.txt"
+        )),
+    ),
+    (
+        "generic-number",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/generic-number.txt"
+        )),
+    ),
+];
+
+/// Names of all templates, user templates first.
+pub fn available() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(USER_TEMPLATE_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    (path.extension()? == "txt")
+                        .then(|| path.file_stem()?.to_str().map(String::from))?
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+
+    for (name, _) in BUILTIN_TEMPLATES {
+        if !names.iter().any(|x| x == name) {
+            names.push(name.into());
+        }
+    }
+
+    names
+}
+
+/// Loads a template by name from the user template directory or the built-in templates.
+pub fn load(name: &str) -> Result<String, String> {
+    let user_path = Path::new(USER_TEMPLATE_DIR).join(format!("{name}.txt"));
+
+    if let Ok(template) = fs::read_to_string(&user_path) {
+        return Ok(template);
+    }
+
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, template)| (*template).to_string())
+        .ok_or_else(|| {
+            format!(
+                "unknown template `{name}`, available templates: {}.",
+                available().join(", ")
+            )
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Values of the placeholders in a template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TemplateContext {
+    pub day: Day,
+    pub year: Option<u16>,
+    /// Title of the puzzle, e.g. `Day 1: Historian Hysteria`.
+    pub title: Option<String>,
+    /// Expected answers of the example input, as found in the puzzle.
+    pub example_part_one: Option<String>,
+    pub example_part_two: Option<String>,
+}
+
+impl TemplateContext {
+    pub fn new(day: Day) -> Self {
+        Self {
+            day,
+            year: None,
+            title: None,
+            example_part_one: None,
+            example_part_two: None,
+        }
+    }
+
+    /// Reads the year from `AOC_YEAR`, the title and the example answers from the downloaded puzzle, if present.
+    pub fn from_project(day: Day) -> Self {
+        let puzzle = fs::read_to_string(puzzle::path(day)).ok();
+        let examples = puzzle.as_deref().map(examples::extract).unwrap_or_default();
+
+        Self {
+            year: env::var("AOC_YEAR").ok().and_then(|x| x.parse().ok()),
+            title: puzzle.as_deref().and_then(puzzle_title),
+            example_part_one: examples.part_1.answer,
+            example_part_two: examples.part_2.answer,
+            ..Self::new(day)
+        }
+    }
+}
+
+/// Replaces the placeholders of a template:
+///  - `%DAY_NUMBER%`: the day without padding, e.g. `1`.
+///  - `%DAY%`: the day padded to two digits, e.g. `01`.
+///  - `%YEAR%`: the configured year, empty if not set.
+///  - `%TITLE%`: the title of the downloaded puzzle, `Day 01` if it was not downloaded.
+///  - `%EXAMPLE_PART_ONE%`, `%EXAMPLE_PART_TWO%`: the expected example answers as `Some(..)`, `None` if not known.
+///    Only unsigned integers are inserted, as the parts return them. Other answers are rendered as `None`,
+///    with an `// expected: ".."` comment at the end of the line, see [`examples::answer_literal`].
+pub fn render(template: &str, context: &TemplateContext) -> String {
+    let examples = [
+        ("%EXAMPLE_PART_ONE%", &context.example_part_one),
+        ("%EXAMPLE_PART_TWO%", &context.example_part_two),
+    ];
+
+    let render_examples = |line: &str| {
+        let mut line = line.to_string();
+        let mut comments = vec![];
+
+        for (placeholder, answer) in examples {
+            if !line.contains(placeholder) {
+                continue;
+            }
+            let value = match answer.as_deref() {
+                Some(answer) => examples::answer_literal(answer).map_or_else(
+                    || {
+                        comments.push(examples::answer_comment(answer));
+                        "None".to_string()
+                    },
+                    |literal| format!("Some({literal})"),
+                ),
+                None => "None".to_string(),
+            };
+            line = line.replace(placeholder, &value);
+        }
+
+        comments
+            .iter()
+            .fold(line, |line, comment| format!("{line} {comment}"))
+    };
+
+    template
+        .split('\n')
+        .map(render_examples)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .replace("%DAY_NUMBER%", &context.day.into_inner().to_string())
+        .replace("%DAY%", &context.day.to_string())
+        .replace(
+            "%YEAR%",
+            &context.year.map(|x| x.to_string()).unwrap_or_default(),
+        )
+        .replace(
+            "%TITLE%",
+            &context
+                .title
+                .clone()
+                .unwrap_or_else(|| format!("Day {}", context.day)),
+        )
+}
+
+/// Reads the title from the heading of a puzzle description, e.g. `## --- Day 1: Historian Hysteria ---`.
+pub fn puzzle_title(puzzle: &str) -> Option<String> {
+    puzzle.lines().find_map(|line| {
+        let title = line
+            .trim_start_matches('#')
+            .trim()
+            .strip_prefix("---")?
+            .strip_suffix("---")?
+            .trim();
+        title.starts_with("Day ").then(|| title.to_string())
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load, puzzle_title, render, TemplateContext, BUILTIN_TEMPLATES};
+    use crate::day;
+
+    #[test]
+    fn renders_placeholders() {
+        let context = TemplateContext {
+            year: Some(2024),
+            title: Some("Day 1: Historian Hysteria".into()),
+            example_part_one: Some("11".into()),
+            ..TemplateContext::new(day!(1))
+        };
+
+        assert_eq!(
+            render(
+                "// %TITLE% (%YEAR%)\nsolution!(%DAY_NUMBER%); // %DAY%\n%EXAMPLE_PART_ONE% %EXAMPLE_PART_TWO%",
+                &context
+            ),
+            "// Day 1: Historian Hysteria (2024)\nsolution!(1); // 01\nSome(11) None"
+        );
+
+        assert_eq!(
+            render("// %TITLE% %YEAR%", &TemplateContext::new(day!(7))),
+            "// Day 07 "
+        );
+
+        // answers that the parts cannot return are not inserted.
+        let context = TemplateContext {
+            example_part_one: Some("-12".into()),
+            example_part_two: Some("abc".into()),
+            ..TemplateContext::new(day!(1))
+        };
+        assert_eq!(
+            render(
+                "assert_eq!(result, %EXAMPLE_PART_ONE%);\nassert_eq!(result, %EXAMPLE_PART_TWO%);\n",
+                &context
+            ),
+            "assert_eq!(result, None); // expected: \"-12\"\nassert_eq!(result, None); // expected: \"abc\"\n"
+        );
+    }
+
+    #[test]
+    fn renders_builtin_templates() {
+        for (name, _) in BUILTIN_TEMPLATES {
+            let rendered = render(&load(name).unwrap(), &TemplateContext::new(day!(3)));
+            assert_eq!(rendered.contains("solution!(3"), true, "{name}");
+            assert_eq!(rendered.contains('%'), false, "{name}");
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_templates() {
+        let err = load("does-not-exist").unwrap_err();
+        assert_eq!(
+            err.contains("plain, grid, parse-once, generic-number"),
+            true
+        );
+    }
+
+    #[test]
+    fn parses_puzzle_titles() {
+        assert_eq!(
+            puzzle_title("\n## --- Day 1: Historian Hysteria ---\n\nThe Chief Historian..."),
+            Some("Day 1: Historian Hysteria".into())
+        );
+        assert_eq!(puzzle_title("--- Part Two ---"), None);
+        assert_eq!(puzzle_title("no title"), None);
+    }
+}
@@ -0,0 +1,61 @@
+//! Size of the terminal and paging of long output.
+
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+const DEFAULT_WIDTH: usize = 80;
+const DEFAULT_HEIGHT: usize = 24;
+
+/// Number of columns of the terminal, from `COLUMNS` or `tput cols`.
+pub fn width() -> usize {
+    size("COLUMNS", "cols").unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Number of lines of the terminal, from `LINES` or `tput lines`.
+pub fn height() -> usize {
+    size("LINES", "lines").unwrap_or(DEFAULT_HEIGHT)
+}
+
+fn size(var: &str, capability: &str) -> Option<usize> {
+    env::var(var)
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .or_else(|| {
+            let output = Command::new("tput")
+                .arg(capability)
+                .stderr(Stdio::inherit())
+                .output()
+                .ok()?;
+            String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+        })
+        .filter(|x| *x > 0)
+}
+
+/// Prints text, through a pager if stdout is a terminal and the text does not fit on the screen.
+/// The pager is read from `PAGER` and defaults to `less -R`, which keeps the ANSI styles.
+pub fn page(text: &str) {
+    if io::stdout().is_terminal() && text.lines().count() >= height() {
+        let pager = env::var("PAGER").unwrap_or_else(|_| "less -R".into());
+        let mut args = pager.split_whitespace();
+
+        if let Some(program) = args.next() {
+            if let Ok(mut child) = Command::new(program)
+                .args(args)
+                .stdin(Stdio::piped())
+                .spawn()
+            {
+                if let Some(mut stdin) = child.stdin.take() {
+                    // the pager may exit before reading everything, e.g. when quit early.
+                    let _ = writeln!(stdin, "{text}");
+                }
+                let _ = child.wait();
+                return;
+            }
+        }
+    }
+
+    println!("{text}");
+}
@@ -1,19 +1,56 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::error::Error;
+use crate::template::{allocations::AllocStats, run_multi::child_commands::parse_duration, Day};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Version of the `timings.json` schema written by [`Timings::store_file`].
+/// Version 1 (no `version` key) stored part times as pre-formatted strings, e.g. `"74.1ms"`.
+/// Version 2 stores them as nanoseconds.
+pub const SCHEMA_VERSION: u32 = 2;
+
 /// Represents benchmark times for a single day.
+/// All times are in nanoseconds, use [`format_nanos`] to display them.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1: Option<f64>,
+    pub part_2: Option<f64>,
+    pub part_1_alloc: Option<AllocStats>,
+    pub part_2_alloc: Option<AllocStats>,
+    pub part_1_samples: Option<u64>,
+    pub part_2_samples: Option<u64>,
+    /// Time of the parse function registered with the `parse(...)` option of `solution!`, not part of the total.
+    pub parse: Option<f64>,
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// A day without any measurements.
+    pub fn new(day: Day) -> Self {
+        Self {
+            day,
+            part_1: None,
+            part_2: None,
+            part_1_alloc: None,
+            part_2_alloc: None,
+            part_1_samples: None,
+            part_2_samples: None,
+            parse: None,
+            total_nanos: 0_f64,
+        }
+    }
+}
+
+/// Formats nanoseconds like the runner prints durations, e.g. `74.1ms`.
+pub fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos as u64);
+    format!("{duration:.1?}")
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -23,18 +60,28 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self) -> Result<(), io::Error> {
         let json = JsonValue::from(self.clone());
         let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Fails if the file cannot be read or parsed, e.g. if it was written by a newer version, so it is
+    /// not overwritten with the timings of a single run.
+    pub fn read_from_file() -> Result<Self, Error> {
+        let context = format!("Failed to read stored benchmarks from \"{TIMINGS_FILE_PATH}\"");
+
+        match fs::read_to_string(TIMINGS_FILE_PATH) {
+            Ok(contents) => Timings::try_from(contents).map_err(|e| {
+                Error::io(
+                    format!("{context}, fix or remove the file"),
+                    io::Error::new(io::ErrorKind::InvalidData, e),
+                )
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(Error::io(context, e)),
+        }
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -51,7 +98,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -65,6 +112,18 @@ impl Timings {
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    /// Returns `true` if a parse time was recorded for any day.
+    pub fn has_parse_times(&self) -> bool {
+        self.data.iter().any(|t| t.parse.is_some())
+    }
+
+    /// Returns `true` if allocation stats were recorded for any day.
+    pub fn has_alloc_stats(&self) -> bool {
+        self.data
+            .iter()
+            .any(|t| t.part_1_alloc.is_some() || t.part_2_alloc.is_some())
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -73,6 +132,10 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(SCHEMA_VERSION)),
+        );
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -88,9 +151,26 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let version = match json.get("version") {
+            None => 1,
+            Some(v) => v
+                .get::<f64>()
+                .map(|v| *v as u32)
+                .ok_or("expected `json.version` to be a number.")?,
+        };
+
+        if version > SCHEMA_VERSION {
+            return Err(format!(
+                "timings schema version {version} is newer than the supported version {SCHEMA_VERSION}."
+            ));
+        }
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
@@ -99,12 +179,33 @@ impl TryFrom<String> for Timings {
         Ok(Timings {
             data: json_data
                 .iter()
-                .map(Timing::try_from)
+                .map(|timing| match version {
+                    1 => migrate_v1(timing).and_then(|timing| Timing::try_from(&timing)),
+                    _ => Timing::try_from(timing),
+                })
                 .collect::<Result<_, _>>()?,
         })
     }
 }
 
+/// Converts a timing stored with schema version 1 to the current schema by parsing its formatted durations.
+fn migrate_v1(value: &JsonValue) -> Result<JsonValue, String> {
+    let mut json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected timing to be a JSON object.")?
+        .clone();
+
+    for key in ["part_1", "part_2", "parse"] {
+        if let Some(JsonValue::String(formatted)) = json.get(key) {
+            let nanos = parse_duration(formatted)
+                .ok_or(format!("Could not migrate timing.{key} `{formatted}`."))?;
+            json.insert(key.into(), JsonValue::Number(nanos));
+        }
+    }
+
+    Ok(JsonValue::Object(json))
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<&Timing> for JsonValue {
@@ -114,8 +215,8 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        let part_1 = value.part_1.map(JsonValue::Number);
+        let part_2 = value.part_2.map(JsonValue::Number);
 
         map.insert(
             "part_1".into(),
@@ -133,6 +234,29 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, alloc) in [
+            ("part_1_alloc", &value.part_1_alloc),
+            ("part_2_alloc", &value.part_2_alloc),
+        ] {
+            if let Some(alloc) = alloc {
+                map.insert(key.into(), JsonValue::from(alloc));
+            }
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        for (key, samples) in [
+            ("part_1_samples", value.part_1_samples),
+            ("part_2_samples", value.part_2_samples),
+        ] {
+            if let Some(samples) = samples {
+                map.insert(key.into(), JsonValue::Number(samples as f64));
+            }
+        }
+
+        if let Some(parse) = value.parse {
+            map.insert("parse".into(), JsonValue::Number(parse));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -153,13 +277,45 @@ impl TryFrom<&JsonValue> for Timing {
 
         let part_1 = json
             .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+            .and_then(|v| {
+                if v.is_null() {
+                    Some(None)
+                } else {
+                    v.get::<f64>().map(Some)
+                }
+            })
+            .ok_or("Expected timing.part_1 to be null or a number.")?;
 
         let part_2 = json
             .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+            .and_then(|v| {
+                if v.is_null() {
+                    Some(None)
+                } else {
+                    v.get::<f64>().map(Some)
+                }
+            })
+            .ok_or("Expected timing.part_2 to be null or a number.")?;
+
+        // allocation stats are optional and missing in timings stored by earlier versions.
+        let alloc = |key: &str| {
+            json.get(key)
+                .filter(|v| !v.is_null())
+                .map(AllocStats::try_from)
+                .transpose()
+        };
+
+        let part_1_alloc = alloc("part_1_alloc")?;
+        let part_2_alloc = alloc("part_2_alloc")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+        };
+
+        let parse = json.get("parse").and_then(|v| v.get::<f64>()).copied();
 
         let total_nanos = json
             .get("total_nanos")
@@ -168,8 +324,13 @@ impl TryFrom<&JsonValue> for Timing {
 
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            part_1: part_1.copied(),
+            part_2: part_2.copied(),
+            part_1_alloc,
+            part_2_alloc,
+            part_1_samples: samples("part_1_samples"),
+            part_2_samples: samples("part_2_samples"),
+            parse,
             total_nanos,
         })
     }
@@ -187,22 +348,21 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(10_000_000_f64),
+                    part_2: Some(20_000_000_f64),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(30_000_000_f64),
+                    part_2: Some(40_000_000_f64),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
+                    part_1: Some(40_000_000_f64),
                     total_nanos: 4e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
@@ -213,16 +373,61 @@ mod tests {
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": 1000000, "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1, Some(1_000_000_f64));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_alloc_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": 1000000, "part_2": null, "part_1_alloc": { "blocks": 3, "bytes": 276, "peak_bytes": 232 }, "total_nanos": 1000000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_alloc.unwrap().bytes, 276);
+            assert_eq!(timing.part_2_alloc, None);
+            assert_eq!(timings.has_alloc_stats(), true);
+        }
+
+        #[test]
+        fn handles_json_samples_and_parse_time() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": 1000000, "part_2": 2000000, "part_1_samples": 100, "parse": 50000, "total_nanos": 3000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_samples, Some(100));
+            assert_eq!(timing.part_2_samples, None);
+            assert_eq!(timing.parse, Some(50_000_f64));
+            assert_eq!(timings.has_parse_times(), true);
+        }
+
+        #[test]
+        fn migrates_v1_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "74.1ms", "part_2": null, "parse": "12.0µs", "total_nanos": 74100000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1, Some(74_100_000_f64));
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.parse, Some(12_000_f64));
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unmigratable_v1_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "fast", "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_newer_schema_versions() {
+            let json = r#"{ "version": 99, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -247,9 +452,29 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::{format_nanos, Timings, SCHEMA_VERSION};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        #[test]
+        fn round_trips_timings() {
+            let json = JsonValue::from(get_mock_timings()).stringify().unwrap();
+            assert_eq!(
+                json.contains(&format!(r#""version":{SCHEMA_VERSION}"#)),
+                true
+            );
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data.len(), 3);
+            assert_eq!(timings.data[0].part_1, Some(10_000_000_f64));
+            assert_eq!(timings.data[2].part_2, None);
+        }
+
+        #[test]
+        fn formats_nanos() {
+            assert_eq!(format_nanos(74_130_000_f64), "74.1ms");
+            assert_eq!(format_nanos(39_f64), "39.0ns");
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -278,42 +503,36 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: Some(1_000_000_f64),
+                    part_2: Some(2_000_000_f64),
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
+                    part_1: Some(1_000_000_f64),
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![Timing::new(day!(1))],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -329,12 +548,7 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -349,12 +563,7 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
             };
             let merged = timings.merge(&other);
 